@@ -0,0 +1,196 @@
+use thiserror::Error;
+
+use crate::math::modulus::Modulus;
+use crate::math::poly::Poly;
+use crate::math::polyvec::PolyVec;
+use crate::math::ring::Ring;
+use crate::SchemePoly;
+
+// =====================
+// Wire Format
+// little-endian bit packing, `bits` per coefficient
+// =====================
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CodecError {
+    #[error("wrong length: expected {expected} bytes, got {got}")]
+    Length { expected: usize, got: usize },
+
+    #[error("coefficient {index} out of range: {value}")]
+    Coefficient { index: usize, value: i32 },
+
+    #[error("non-canonical encoding")]
+    NonCanonical,
+}
+
+pub fn check_len(bytes: &[u8], expected: usize) -> Result<(), CodecError> {
+    if bytes.len() != expected {
+        return Err(CodecError::Length { expected, got: bytes.len() });
+    }
+    Ok(())
+}
+
+// `count` coefficients, rounded up to whole bytes
+pub const fn packed_len_for(count: usize, bits: u32) -> usize {
+    (count * bits as usize).div_ceil(8)
+}
+
+pub fn pack(coeffs: &[i32], bits: u32) -> Vec<u8> {
+    let mut out = vec![0u8; packed_len_for(coeffs.len(), bits)];
+    let mut pos = 0usize;
+
+    for &c in coeffs.iter() {
+        for b in 0..bits {
+            out[pos / 8] |= (((c >> b) & 1) as u8) << (pos % 8);
+            pos += 1;
+        }
+    }
+    out
+}
+
+pub fn unpack<const N: usize>(bytes: &[u8], bits: u32) -> Result<[i32; N], CodecError> {
+    let mut coeffs = [0i32; N];
+    coeffs.copy_from_slice(&unpack_slice(bytes, N, bits)?);
+    Ok(coeffs)
+}
+
+pub fn unpack_slice(bytes: &[u8], count: usize, bits: u32) -> Result<Vec<i32>, CodecError> {
+    check_len(bytes, packed_len_for(count, bits))?;
+
+    let mut coeffs = vec![0i32; count];
+    let mut pos = 0usize;
+
+    for c in coeffs.iter_mut() {
+        for b in 0..bits {
+            *c |= (((bytes[pos / 8] >> (pos % 8)) & 1) as i32) << b;
+            pos += 1;
+        }
+    }
+    Ok(coeffs)
+}
+
+// =====================
+// Compression
+// compress(x) = round(2^d x / q) mod 2^d
+// decompress(y) = round(q y / 2^d)
+// =====================
+// the coefficients are secret-dependent, so no hardware division
+pub fn compress(x: i32, d: u32, m: Modulus) -> i32 {
+    let x = m.reduce_signed(x as i64) as u64;
+    (m.divrem((x << d) + m.q() as u64 / 2).0 & ((1 << d) - 1)) as i32
+}
+
+pub fn decompress(y: i32, d: u32, m: Modulus) -> i32 {
+    ((y as i64 * m.q() as i64 + (1 << (d - 1))) >> d) as i32
+}
+
+impl<const N: usize, const Q: u32, R: Ring> Poly<N, Q, R> {
+    pub const COEFF_BITS: u32 = Self::MODULUS.bits(); // ceil(log2 Q)
+    pub const BYTES: usize = packed_len_for(N, Self::COEFF_BITS);
+
+    // =====================
+    // ceil(log2 Q)-bit Serialization
+    // =====================
+    pub fn to_bytes(&self) -> Vec<u8> {
+        pack(&self.to_canonical().coeffs, Self::COEFF_BITS)
+    }
+
+    // strict: exact length, every coefficient in [0, Q)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        let coeffs = unpack::<N>(bytes, Self::COEFF_BITS)?;
+
+        if let Some(index) = coeffs.iter().position(|&c| c >= Q as i32) {
+            return Err(CodecError::Coefficient { index, value: coeffs[index] });
+        }
+        Ok(Self::from_coeffs(coeffs))
+    }
+
+    // =====================
+    // Lossy d-bit Compression
+    // d >= COEFF_BITS means no compression
+    // =====================
+    pub fn compressed_len(d: u32) -> usize {
+        if d >= Self::COEFF_BITS {
+            Self::BYTES
+        } else {
+            packed_len_for(N, d)
+        }
+    }
+
+    pub fn to_compressed_bytes(&self, d: u32) -> Vec<u8> {
+        if d >= Self::COEFF_BITS {
+            return self.to_bytes();
+        }
+
+        let mut c = self.coeffs;
+        for x in c.iter_mut() {
+            *x = compress(*x, d, Self::MODULUS);
+        }
+        pack(&c, d)
+    }
+
+    pub fn from_compressed_bytes(bytes: &[u8], d: u32) -> Result<Self, CodecError> {
+        if d >= Self::COEFF_BITS {
+            return Self::from_bytes(bytes);
+        }
+
+        let mut coeffs = unpack::<N>(bytes, d)?;
+        for y in coeffs.iter_mut() {
+            *y = decompress(*y, d, Self::MODULUS);
+        }
+        Ok(Self::from_coeffs(coeffs))
+    }
+
+    // the value the receiver sees after a compress/decompress round trip
+    pub fn compress_round_trip(&self, d: u32) -> Self {
+        if d >= Self::COEFF_BITS {
+            return self.clone();
+        }
+
+        let mut r = Self::zero();
+        for (o, &x) in r.coeffs.iter_mut().zip(self.coeffs.iter()) {
+            *o = decompress(compress(x, d, Self::MODULUS), d, Self::MODULUS);
+        }
+        r
+    }
+}
+
+impl<const K: usize, const N: usize, const Q: u32> PolyVec<K, N, Q> {
+    pub const BYTES: usize = K * SchemePoly::<N, Q>::BYTES;
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.polys.iter().flat_map(|p| p.to_bytes()).collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        check_len(bytes, Self::BYTES)?;
+
+        let mut polys = Vec::with_capacity(K);
+        for chunk in bytes.chunks(SchemePoly::<N, Q>::BYTES) {
+            polys.push(SchemePoly::from_bytes(chunk)?);
+        }
+        Ok(Self::from_fn(|i| polys[i].clone()))
+    }
+
+    pub fn compressed_len(d: u32) -> usize {
+        K * SchemePoly::<N, Q>::compressed_len(d)
+    }
+
+    pub fn to_compressed_bytes(&self, d: u32) -> Vec<u8> {
+        self.polys.iter().flat_map(|p| p.to_compressed_bytes(d)).collect()
+    }
+
+    pub fn from_compressed_bytes(bytes: &[u8], d: u32) -> Result<Self, CodecError> {
+        let len = SchemePoly::<N, Q>::compressed_len(d);
+        check_len(bytes, K * len)?;
+
+        let mut polys = Vec::with_capacity(K);
+        for chunk in bytes.chunks(len) {
+            polys.push(SchemePoly::from_compressed_bytes(chunk, d)?);
+        }
+        Ok(Self::from_fn(|i| polys[i].clone()))
+    }
+
+    pub fn compress_round_trip(&self, d: u32) -> Self {
+        Self::from_fn(|i| self.polys[i].compress_round_trip(d))
+    }
+}
@@ -0,0 +1,96 @@
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes256;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+pub const ENTROPY_BYTES: usize = 48;
+
+// =====================
+// NIST AES-256 CTR_DRBG (no derivation function)
+// the randombytes() behind the PQC submission KAT files
+// =====================
+pub struct NistDrbg {
+    key: [u8; 32],
+    v: [u8; 16],
+}
+
+impl NistDrbg {
+    pub fn new(entropy: &[u8; ENTROPY_BYTES], personalization: Option<&[u8; ENTROPY_BYTES]>) -> Self {
+        let mut seed = *entropy;
+        if let Some(p) = personalization {
+            seed.iter_mut().zip(p).for_each(|(s, p)| *s ^= p);
+        }
+
+        let mut drbg = Self { key: [0u8; 32], v: [0u8; 16] };
+        drbg.update(Some(&seed));
+        seed.zeroize();
+        drbg
+    }
+
+    // V is a 128-bit big-endian counter
+    fn next_block(&mut self) -> [u8; 16] {
+        for b in self.v.iter_mut().rev() {
+            *b = b.wrapping_add(1);
+            if *b != 0 {
+                break;
+            }
+        }
+
+        let mut block = GenericArray::from(self.v);
+        Aes256::new(GenericArray::from_slice(&self.key)).encrypt_block(&mut block);
+        block.into()
+    }
+
+    // (Key, V) = first 48 bytes of the keystream, xor provided data
+    fn update(&mut self, provided: Option<&[u8; ENTROPY_BYTES]>) {
+        let mut temp = [0u8; ENTROPY_BYTES];
+        for chunk in temp.chunks_mut(16) {
+            chunk.copy_from_slice(&self.next_block());
+        }
+        if let Some(p) = provided {
+            temp.iter_mut().zip(p).for_each(|(t, p)| *t ^= p);
+        }
+
+        self.key.copy_from_slice(&temp[..32]);
+        self.v.copy_from_slice(&temp[32..]);
+        temp.zeroize();
+    }
+}
+
+// one fill_bytes == one randombytes() call, including the trailing update
+impl RngCore for NistDrbg {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(16) {
+            let block = self.next_block();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        self.update(None);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for NistDrbg {}
+
+impl Drop for NistDrbg {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.v.zeroize();
+    }
+}
@@ -0,0 +1,69 @@
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Sha3_512, Shake128};
+
+use crate::crypto::noise::{NoiseSeed, SEED_BYTES};
+use crate::crypto::secret::Secret;
+use crate::math::polyvec::PolyMatrix;
+use crate::SchemePoly;
+
+pub type PublicSeed = [u8; SEED_BYTES];
+
+// =====================
+// Key Seed Split
+// (rho, sigma) = SHA3-512(d): rho is public, sigma feeds the noise PRF
+// =====================
+pub fn split_seed(d: &[u8; SEED_BYTES]) -> (PublicSeed, Secret<NoiseSeed>) {
+    let digest = Secret::new(<[u8; 2 * SEED_BYTES]>::from(Sha3_512::digest(d)));
+
+    let mut rho = [0u8; SEED_BYTES];
+    let mut sigma = [0u8; SEED_BYTES];
+    rho.copy_from_slice(&digest.expose()[..SEED_BYTES]);
+    sigma.copy_from_slice(&digest.expose()[SEED_BYTES..]);
+
+    (rho, Secret::new(sigma))
+}
+
+// =====================
+// Uniform Poly from Seed
+// SHAKE128(rho || j || i) read as a little-endian bit stream,
+// ceil(log2 Q)-bit candidates, reject >= Q; for Q = 3329 that
+// is Kyber's two 12-bit candidates per 3 bytes
+// =====================
+pub fn expand_poly<const N: usize, const Q: u32>(rho: &PublicSeed, i: u8, j: u8) -> SchemePoly<N, Q> {
+    let mut xof = Shake128::default();
+    xof.update(rho);
+    xof.update(&[j, i]);
+    let mut reader = xof.finalize_xof();
+
+    let bits = SchemePoly::<N, Q>::COEFF_BITS;
+    let mut p = SchemePoly::zero();
+    let mut filled = 0;
+    let mut byte = [0u8; 1];
+    let (mut acc, mut have) = (0u32, 0);
+
+    while filled < N {
+        while have < bits {
+            reader.read(&mut byte);
+            acc |= (byte[0] as u32) << have;
+            have += 8;
+        }
+
+        let d = acc & ((1 << bits) - 1);
+        acc >>= bits;
+        have -= bits;
+
+        if d < Q {
+            p.coeffs[filled] = d as i32;
+            filled += 1;
+        }
+    }
+
+    p
+}
+
+// =====================
+// Public Matrix A[i][j] from Seed
+// =====================
+pub fn expand_matrix<const K: usize, const N: usize, const Q: u32>(rho: &PublicSeed) -> PolyMatrix<K, N, Q> {
+    PolyMatrix::from_fn(|i, j| expand_poly(rho, i as u8, j as u8))
+}
@@ -0,0 +1,303 @@
+use rand::{CryptoRng, RngCore};
+
+use crate::crypto::codec::{compress, decompress};
+use crate::crypto::kem::{Message, MSG_BYTES};
+use crate::crypto::mlwe;
+use crate::crypto::noise;
+use crate::crypto::params::{Dist, Params};
+use crate::math::modulus::Modulus;
+use crate::math::ring::Ring;
+use crate::SchemeRing;
+
+// =====================
+// Decryption Failure Analysis
+// decrypt sees m + d with
+//   d = e·r - s·(e1 + cu) + e2 + cv
+// (cu / cv = compression noise of u / v) and fails once |d| >= q/4.
+// Coefficients of d are treated as sums of independent products,
+// the same assumption as the Kyber failure scripts; only the first
+// MSG_BYTES * 8 coefficients carry message bits
+// =====================
+pub fn threshold(params: &Params) -> i32 {
+    params.q as i32 / 4
+}
+
+// probabilities below this are dropped from the laws
+const PRUNE: f64 = 1e-300;
+
+// discrete law on [min, min + p.len())
+#[derive(Clone)]
+struct Law {
+    min: i32,
+    p: Vec<f64>,
+}
+
+impl Law {
+    fn point(x: i32) -> Self {
+        Self { min: x, p: vec![1.0] }
+    }
+
+    fn from_pairs(pairs: impl IntoIterator<Item = (i32, f64)>) -> Self {
+        let pairs: Vec<(i32, f64)> = pairs.into_iter().collect();
+        let min = pairs.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let max = pairs.iter().map(|&(x, _)| x).max().unwrap_or(0);
+
+        let mut p = vec![0.0; (max - min + 1) as usize];
+        for (x, px) in pairs {
+            p[(x - min) as usize] += px;
+        }
+        Self { min, p }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (i32, f64)> + '_ {
+        (self.min..).zip(self.p.iter().copied())
+    }
+
+    // law of X + Y
+    fn convolve(&self, other: &Self) -> Self {
+        let mut p = vec![0.0; self.p.len() + other.p.len() - 1];
+        for (i, &a) in self.p.iter().enumerate() {
+            for (j, &b) in other.p.iter().enumerate() {
+                p[i + j] += a * b;
+            }
+        }
+        Self { min: self.min + other.min, p }.pruned()
+    }
+
+    // law of X * Y
+    fn product(&self, other: &Self) -> Self {
+        Self::from_pairs(
+            self.iter()
+                .flat_map(|(x, px)| other.iter().map(move |(y, py)| (x * y, px * py))),
+        )
+    }
+
+    // law of X_1 + ... + X_n, by repeated squaring
+    fn pow(&self, mut n: usize) -> Self {
+        let mut acc = Self::point(0);
+        let mut base = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                acc = acc.convolve(&base);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.convolve(&base);
+            }
+        }
+        acc
+    }
+
+    fn pruned(mut self) -> Self {
+        let first = self.p.iter().position(|&x| x > PRUNE).unwrap_or(0);
+        let last = self.p.iter().rposition(|&x| x > PRUNE).unwrap_or(0);
+        self.p = self.p[first..=last].to_vec();
+        self.min += first as i32;
+        self
+    }
+
+    // P(|X + Y| >= t) for Y ~ other
+    fn tail_with(&self, other: &Self, t: i32) -> f64 {
+        // below[i] = P(X < min + i), above[i] = P(X >= min + i), both summed from the tails
+        let len = self.p.len();
+        let mut below = vec![0.0; len + 1];
+        let mut above = vec![0.0; len + 1];
+        for i in 0..len {
+            below[i + 1] = below[i] + self.p[i];
+            above[len - 1 - i] = above[len - i] + self.p[len - 1 - i];
+        }
+
+        let index = |x: i32| (x - self.min).clamp(0, len as i32) as usize;
+        other
+            .iter()
+            .map(|(y, py)| py * (below[index(-t - y + 1)] + above[index(t - y)]))
+            .sum()
+    }
+}
+
+fn dist_law(dist: Dist) -> Law {
+    match dist {
+        Dist::Ternary => Law::from_pairs((-1..=1).map(|x| (x, 1.0 / 3.0))),
+        Dist::Uniform(w) => Law::from_pairs((-w..=w).map(|x| (x, 1.0 / (2 * w + 1) as f64))),
+        Dist::Cbd(eta) => {
+            let eta = eta as i32;
+            let choose = |n: i32, k: i32| (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64);
+            let scale = 4f64.powi(eta);
+            Law::from_pairs((-eta..=eta).map(|x| (x, choose(2 * eta, eta + x) / scale)))
+        }
+    }
+}
+
+// decompress(compress(x)) - x over uniform x, centered
+fn compression_law(d: u32, m: Modulus) -> Law {
+    if d >= m.bits() {
+        return Law::point(0);
+    }
+
+    let q = m.q() as i32;
+    Law::from_pairs((0..q).map(|x| {
+        let mut err = decompress(compress(x, d, m), d, m) - x;
+        if err > q / 2 {
+            err -= q;
+        } else if err < -q / 2 {
+            err += q;
+        }
+        (err, 1.0 / q as f64)
+    }))
+}
+
+// products a_i·b_j landing on each coefficient of a ring product:
+// fold the pair counts with |c_i| so nothing cancels
+fn term_counts<R: Ring>(n: usize) -> Vec<usize> {
+    let mut wide: Vec<usize> = (0..2 * n).map(|k| if k < n { k + 1 } else { 2 * n - 1 - k }).collect();
+    for k in (n..2 * n).rev() {
+        let val = wide[k];
+        for &(i, c) in R::TAIL {
+            wide[k - n + i] += c.unsigned_abs() as usize * val;
+        }
+    }
+
+    wide.truncate(n);
+    wide
+}
+
+// =====================
+// Estimator
+// =====================
+pub struct Estimate {
+    pub threshold: i32,
+    pub per_coeff: Vec<f64>, // P(|d_i| >= threshold), one per ring coefficient
+    pub per_message: f64,    // P(any message |d_i| >= threshold), coefficients independent
+    pub union_bound: f64,    // sum of the message per_coeff, capped at 1
+}
+
+impl Estimate {
+    pub fn worst_coeff(&self) -> f64 {
+        self.per_coeff.iter().copied().fold(0.0, f64::max)
+    }
+
+    pub fn mean_coeff(&self) -> f64 {
+        self.per_coeff.iter().sum::<f64>() / self.per_coeff.len() as f64
+    }
+}
+
+pub fn estimate(params: &Params) -> Estimate {
+    estimate_with_threshold(params, threshold(params))
+}
+
+pub fn estimate_with_threshold(params: &Params, threshold: i32) -> Estimate {
+    params.assert_valid();
+    let m = Modulus::new(params.q);
+
+    let secret = dist_law(params.secret);
+    let noise = dist_law(params.noise);
+
+    // one term of e·r - s·(e1 + cu) per module component
+    let er = noise.product(&secret);
+    let su = secret.product(&noise.convolve(&compression_law(params.du, m)));
+    let term = er.convolve(&su).pow(params.k);
+
+    // e2 + cv
+    let ev = noise.convolve(&compression_law(params.dv, m));
+
+    // one law per distinct term count; x^n + 1 has n everywhere,
+    // the trinomials range from n up to 2n - 1
+    let counts = term_counts::<SchemeRing>(params.n);
+    let min_terms = counts.iter().copied().min().unwrap_or(params.n);
+    let max_terms = counts.iter().copied().max().unwrap_or(params.n);
+
+    let mut acc = term.pow(min_terms);
+    let mut tails = Vec::with_capacity(max_terms - min_terms + 1);
+    for m in min_terms..=max_terms {
+        if m > min_terms {
+            acc = acc.convolve(&term);
+        }
+        tails.push(acc.tail_with(&ev, threshold));
+    }
+
+    let per_coeff: Vec<f64> = counts.iter().map(|&c| tails[c - min_terms]).collect();
+    let message = &per_coeff[..MSG_BYTES * 8];
+
+    // 1 - prod(1 - p_i), through logs so 2^-100 does not round to 0
+    let log_pass: f64 = message.iter().map(|&p| (-p).ln_1p()).sum();
+
+    Estimate {
+        threshold,
+        per_message: -log_pass.exp_m1(),
+        union_bound: message.iter().sum::<f64>().min(1.0),
+        per_coeff,
+    }
+}
+
+// =====================
+// Monte-Carlo Simulator
+// runs the real keygen / encrypt and measures d directly
+// =====================
+pub struct Simulation {
+    pub trials: usize,
+    pub n: usize,
+    pub coeff_failures: usize,    // coefficients with |d| >= threshold
+    pub coeff_failures_sq: usize, // sum over trials of (failures in the trial)^2
+    pub message_failures: usize,  // messages with at least one of those
+    pub decrypt_failures: usize,  // messages decrypt actually got wrong
+}
+
+impl Simulation {
+    pub fn per_coeff(&self) -> f64 {
+        self.coeff_failures as f64 / (self.trials * self.n) as f64
+    }
+
+    pub fn per_message(&self) -> f64 {
+        self.message_failures as f64 / self.trials as f64
+    }
+
+    // coefficients of one trial share s, e and r, so the standard
+    // error comes from the spread between trials, not a binomial
+    pub fn per_coeff_std_error(&self) -> f64 {
+        let t = self.trials as f64;
+        let mean = self.coeff_failures as f64 / t;
+        let var = (self.coeff_failures_sq as f64 / t - mean * mean) * t / (t - 1.0);
+        (var.max(0.0) / t).sqrt() / self.n as f64
+    }
+
+    pub fn per_message_std_error(&self) -> f64 {
+        let p = self.per_message();
+        (p * (1.0 - p) / self.trials as f64).sqrt()
+    }
+}
+
+pub fn simulate<const K: usize, const N: usize, const Q: u32>(
+    params: &Params,
+    threshold: i32,
+    trials: usize,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Simulation {
+    let m = Modulus::new(Q);
+    let mut sim = Simulation {
+        trials,
+        n: N,
+        coeff_failures: 0,
+        coeff_failures_sq: 0,
+        message_failures: 0,
+        decrypt_failures: 0,
+    };
+
+    for _ in 0..trials {
+        let (pk, sk) = mlwe::keygen_with_rng::<K, N, Q>(params, rng);
+
+        let mut msg: Message = [0u8; MSG_BYTES];
+        rng.fill_bytes(&mut msg);
+        let ct = mlwe::encrypt(&pk, &msg, &noise::fresh_seed(rng), params);
+
+        let d = mlwe::noise_term(&ct, &sk, &msg, params);
+        let over: Vec<bool> = d.expose().coeffs.iter().map(|&c| m.center(c).abs() >= threshold).collect();
+        let bad = over.iter().filter(|&&x| x).count();
+
+        sim.coeff_failures += bad;
+        sim.coeff_failures_sq += bad * bad;
+        sim.message_failures += over[..MSG_BYTES * 8].contains(&true) as usize;
+        sim.decrypt_failures += (mlwe::decrypt(&ct, &sk, params) != msg) as usize;
+    }
+
+    sim
+}
@@ -0,0 +1,155 @@
+use std::fmt::Write;
+
+use rand::RngCore;
+use thiserror::Error;
+
+use crate::crypto::drbg::{NistDrbg, ENTROPY_BYTES};
+use crate::crypto::kem::{self, Pke};
+
+#[derive(Error, Debug)]
+pub enum KatError {
+    #[error("malformed KAT file at line {line}")]
+    Parse { line: usize },
+
+    #[error("KAT count {count}: {field} mismatch")]
+    Mismatch { count: usize, field: &'static str },
+}
+
+// =====================
+// Known-Answer Vectors
+// same layout and seeding as PQCgenKAT_kem: a DRBG keyed with
+// 0, 1, ..., 47 hands out one 48-byte seed per count, and each
+// count re-seeds the DRBG before keygen + encaps
+// =====================
+struct Vector {
+    count: usize,
+    seed: [u8; ENTROPY_BYTES],
+    fields: [(&'static str, Vec<u8>); 4],
+}
+
+// count plus the raw `key = value` fields that follow it
+type Record = (usize, Vec<(String, Vec<u8>)>);
+
+fn seeds(count: usize) -> Vec<[u8; ENTROPY_BYTES]> {
+    let mut entropy = [0u8; ENTROPY_BYTES];
+    entropy.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+
+    let mut drbg = NistDrbg::new(&entropy, None);
+    (0..count)
+        .map(|_| {
+            let mut seed = [0u8; ENTROPY_BYTES];
+            drbg.fill_bytes(&mut seed);
+            seed
+        })
+        .collect()
+}
+
+// keygen + encaps under a DRBG seeded with `seed`; decaps must agree
+fn run<P: Pke>(pke: &P, count: usize, seed: &[u8; ENTROPY_BYTES]) -> Result<Vector, KatError> {
+    let mut drbg = NistDrbg::new(seed, None);
+
+    let (pk, sk) = kem::keygen_with_rng(pke, &mut drbg);
+    let (ct, ss) = kem::encaps_with_rng(pke, &pk, &mut drbg);
+
+    if kem::decaps(pke, &sk, &ct) != ss {
+        return Err(KatError::Mismatch { count, field: "decaps" });
+    }
+
+    Ok(Vector {
+        count,
+        seed: *seed,
+        fields: [
+            ("pk", pke.public_key_bytes(&pk)),
+            ("sk", sk.to_bytes(pke).expose().clone()),
+            ("ct", pke.ciphertext_bytes(&ct)),
+            ("ss", ss.to_vec()),
+        ],
+    })
+}
+
+// =====================
+// Generate (.rsp)
+// =====================
+pub fn generate<P: Pke>(pke: &P, name: &str, count: usize) -> Result<String, KatError> {
+    let mut out = format!("# {}\n\n", name);
+
+    for (i, seed) in seeds(count).iter().enumerate() {
+        let v = run(pke, i, seed)?;
+
+        // writing into a String cannot fail
+        let _ = writeln!(out, "count = {}", v.count);
+        let _ = writeln!(out, "seed = {}", hex::encode_upper(v.seed));
+        for (field, bytes) in &v.fields {
+            let _ = writeln!(out, "{} = {}", field, hex::encode_upper(bytes));
+        }
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+// =====================
+// Check (.rsp)
+// replays every count and compares field by field
+// =====================
+pub fn check<P: Pke>(pke: &P, rsp: &str) -> Result<usize, KatError> {
+    let mut checked = 0;
+    let mut expected: Option<Record> = None;
+
+    // a trailing empty line closes the last record
+    for (line_no, line) in rsp.lines().chain([""]).enumerate() {
+        let line = line.trim();
+        let parse_err = || KatError::Parse { line: line_no + 1 };
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if line.is_empty() {
+            if let Some((count, fields)) = expected.take() {
+                check_record(pke, count, &fields)?;
+                checked += 1;
+            }
+            continue;
+        }
+
+        let (key, value) = line.split_once(" = ").ok_or_else(parse_err)?;
+        match (key, expected.as_mut()) {
+            ("count", None) => {
+                let count = value.parse().map_err(|_| parse_err())?;
+                expected = Some((count, Vec::new()));
+            }
+            (_, Some((_, fields))) => {
+                let bytes = hex::decode(value).map_err(|_| parse_err())?;
+                fields.push((key.to_string(), bytes));
+            }
+            _ => return Err(parse_err()),
+        }
+    }
+
+    Ok(checked)
+}
+
+fn check_record<P: Pke>(pke: &P, count: usize, fields: &[(String, Vec<u8>)]) -> Result<(), KatError> {
+    let get = |name: &'static str| {
+        fields
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v)
+            .ok_or(KatError::Mismatch { count, field: name })
+    };
+
+    let seed: [u8; ENTROPY_BYTES] = get("seed")?
+        .as_slice()
+        .try_into()
+        .map_err(|_| KatError::Mismatch { count, field: "seed" })?;
+
+    let actual = run(pke, count, &seed)?;
+    for (field, bytes) in &actual.fields {
+        if get(field)? != bytes {
+            return Err(KatError::Mismatch { count, field });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use rand::{CryptoRng, RngCore};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Sha3_256, Sha3_512, Shake256};
+
+use crate::crypto::noise::{NoiseSeed, SEED_BYTES};
+use crate::crypto::secret::Secret;
+use crate::math::ct::{ct_eq, ct_select};
+
+pub const MSG_BYTES: usize = 32;
+pub const KEY_BYTES: usize = 32;
+
+pub type Message = [u8; MSG_BYTES];
+pub type SharedKey = [u8; KEY_BYTES];
+
+// =====================
+// Deterministic IND-CPA PKE
+// everything random comes in through `d` / `coins`,
+// so decaps can re-run encrypt bit-for-bit
+// =====================
+pub trait Pke {
+    type PublicKey: Clone;
+    type SecretKey;
+    type Ciphertext;
+
+    fn keygen(&self, d: &[u8; SEED_BYTES]) -> (Self::PublicKey, Self::SecretKey);
+    fn encrypt(&self, pk: &Self::PublicKey, m: &Message, coins: &NoiseSeed) -> Self::Ciphertext;
+    fn decrypt(&self, sk: &Self::SecretKey, ct: &Self::Ciphertext) -> Message;
+
+    fn public_key_bytes(&self, pk: &Self::PublicKey) -> Vec<u8>;
+    fn secret_key_bytes(&self, sk: &Self::SecretKey) -> Secret<Vec<u8>>;
+    fn ciphertext_bytes(&self, ct: &Self::Ciphertext) -> Vec<u8>;
+}
+
+// =====================
+// FO KEM Keys
+// =====================
+pub struct KemSecretKey<P: Pke> {
+    pub sk: P::SecretKey,
+    pub pk: P::PublicKey,
+    pub h_pk: [u8; 32],
+    pub z: Secret<[u8; 32]>, // implicit-rejection secret
+}
+
+impl<P: Pke> KemSecretKey<P> {
+    // sk || pk || H(pk) || z
+    pub fn to_bytes(&self, pke: &P) -> Secret<Vec<u8>> {
+        let sk = pke.secret_key_bytes(&self.sk);
+        Secret::new(
+            [
+                &sk.expose()[..],
+                &pke.public_key_bytes(&self.pk),
+                &self.h_pk,
+                self.z.expose(),
+            ]
+            .concat(),
+        )
+    }
+}
+
+// =====================
+// Hash Functions
+// H = SHA3-256, G = SHA3-512, J = SHAKE256
+// =====================
+pub fn h(data: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(data).into()
+}
+
+// =====================
+// Shared Key Derivation
+// K = SHA3-256(m || H(c) || H(pk)), so both sides bind the key
+// to this exact ciphertext and recipient
+// =====================
+pub fn kdf(m: &[u8], h_ct: &[u8; 32], h_pk: &[u8; 32]) -> SharedKey {
+    Sha3_256::new()
+        .chain_update(m)
+        .chain_update(h_ct)
+        .chain_update(h_pk)
+        .finalize()
+        .into()
+}
+
+// (K, coins) = G(m || H(pk))
+fn g(m: &Message, h_pk: &[u8; 32]) -> (SharedKey, Secret<NoiseSeed>) {
+    let digest = Secret::new(<[u8; 64]>::from(
+        Sha3_512::new().chain_update(m).chain_update(h_pk).finalize(),
+    ));
+
+    let mut key = [0u8; KEY_BYTES];
+    let mut coins = [0u8; SEED_BYTES];
+    key.copy_from_slice(&digest.expose()[..KEY_BYTES]);
+    coins.copy_from_slice(&digest.expose()[KEY_BYTES..]);
+    (key, Secret::new(coins))
+}
+
+// rejection key J(z || c)
+fn j(z: &[u8; 32], ct: &[u8]) -> SharedKey {
+    let mut xof = Shake256::default();
+    Update::update(&mut xof, z);
+    Update::update(&mut xof, ct);
+
+    let mut key = [0u8; KEY_BYTES];
+    xof.finalize_xof().read(&mut key);
+    key
+}
+
+// =====================
+// KeyGen
+// =====================
+pub fn keygen<P: Pke>(pke: &P) -> (P::PublicKey, KemSecretKey<P>) {
+    keygen_with_rng(pke, &mut rand::thread_rng())
+}
+
+pub fn keygen_with_rng<P: Pke>(
+    pke: &P,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (P::PublicKey, KemSecretKey<P>) {
+    let mut d = [0u8; SEED_BYTES];
+    let mut z = [0u8; 32];
+    rng.fill_bytes(&mut d);
+    rng.fill_bytes(&mut z);
+    let (d, z) = (Secret::new(d), Secret::new(z));
+
+    let (pk, sk) = pke.keygen(d.expose());
+    let h_pk = h(&pke.public_key_bytes(&pk));
+
+    (pk.clone(), KemSecretKey { sk, pk, h_pk, z })
+}
+
+// =====================
+// Encapsulation
+// =====================
+pub fn encaps<P: Pke>(pke: &P, pk: &P::PublicKey) -> (P::Ciphertext, SharedKey) {
+    encaps_with_rng(pke, pk, &mut rand::thread_rng())
+}
+
+pub fn encaps_with_rng<P: Pke>(
+    pke: &P,
+    pk: &P::PublicKey,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (P::Ciphertext, SharedKey) {
+    let mut m: Message = [0u8; MSG_BYTES];
+    rng.fill_bytes(&mut m);
+    let m = Secret::new(m);
+    let h_pk = h(&pke.public_key_bytes(pk));
+
+    let (key, coins) = g(m.expose(), &h_pk);
+    let ct = pke.encrypt(pk, m.expose(), coins.expose());
+
+    (ct, key)
+}
+
+// =====================
+// Decapsulation
+// re-encrypt and compare; on mismatch return J(z || c),
+// which looks random to anyone without z
+// =====================
+pub fn decaps<P: Pke>(pke: &P, sk: &KemSecretKey<P>, ct: &P::Ciphertext) -> SharedKey {
+    let m = Secret::new(pke.decrypt(&sk.sk, ct));
+    let (key, coins) = g(m.expose(), &sk.h_pk);
+
+    let ct_bytes = pke.ciphertext_bytes(ct);
+    let ct_check = pke.ciphertext_bytes(&pke.encrypt(&sk.pk, m.expose(), coins.expose()));
+
+    let reject = j(sk.z.expose(), &ct_bytes);
+    ct_select(ct_eq(&ct_bytes, &ct_check), &key, &reject)
+}
@@ -0,0 +1,395 @@
+use rand::{CryptoRng, RngCore};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake128;
+
+use crate::crypto::codec::{check_len, pack, packed_len_for, unpack_slice, CodecError};
+use crate::crypto::expand::{self, PublicSeed};
+use crate::crypto::kem::{self, Message, Pke, SharedKey, MSG_BYTES};
+use crate::crypto::noise::{self, NoiseSeed, SEED_BYTES};
+use crate::crypto::params::{Dist, LweParams};
+use crate::crypto::secret::Secret;
+use crate::math::modulus::Modulus;
+use crate::Poly;
+
+// =====================
+// Plain LWE (Regev / Frodo style)
+// A is an unstructured n x n matrix over Z_q and the secrets
+// are n x NBAR, so one ciphertext carries NBAR^2 entries of
+// EXTRACTED_BITS message bits each
+// =====================
+pub const NBAR: usize = 8;
+pub const EXTRACTED_BITS: u32 = (MSG_BYTES * 8 / (NBAR * NBAR)) as u32;
+
+// row-major, entries in [0, q)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<i32>,
+}
+
+impl Matrix {
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, data: vec![0; rows * cols] }
+    }
+
+    // row by row, so both operands are walked in memory order;
+    // n products of (q - 1)^2 stay far below 2^64
+    pub fn mul(&self, other: &Self, m: Modulus) -> Self {
+        assert_eq!(self.cols, other.rows, "matrix shapes do not match");
+
+        let mut out = Self::zero(self.rows, other.cols);
+        let mut acc = vec![0u64; other.cols];
+
+        for (i, row) in self.data.chunks(self.cols).enumerate() {
+            acc.iter_mut().for_each(|x| *x = 0);
+            for (&a, other_row) in row.iter().zip(other.data.chunks(other.cols)) {
+                for (x, &b) in acc.iter_mut().zip(other_row) {
+                    *x += a as u64 * b as u64;
+                }
+            }
+            for (o, &x) in out.data[i * other.cols..].iter_mut().zip(acc.iter()) {
+                *o = m.reduce(x);
+            }
+        }
+
+        out
+    }
+
+    pub fn add(&self, other: &Self, m: Modulus) -> Self {
+        self.zip_with(other, |a, b| m.reduce(a as u64 + b as u64))
+    }
+
+    pub fn sub(&self, other: &Self, m: Modulus) -> Self {
+        self.zip_with(other, |a, b| m.reduce(a as u64 + m.q() as u64 - b as u64))
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(i32, i32) -> i32) -> Self {
+        assert_eq!((self.rows, self.cols), (other.rows, other.cols), "matrix shapes do not match");
+
+        let data = self.data.iter().zip(other.data.iter()).map(|(&a, &b)| f(a, b)).collect();
+        Self { rows: self.rows, cols: self.cols, data }
+    }
+
+    // =====================
+    // Serialization
+    // ceil(log2 q) bits per entry
+    // =====================
+    pub fn byte_len(rows: usize, cols: usize, m: Modulus) -> usize {
+        packed_len_for(rows * cols, m.bits())
+    }
+
+    pub fn to_bytes(&self, m: Modulus) -> Vec<u8> {
+        pack(&self.data, m.bits())
+    }
+
+    // strict: entries >= q are rejected, not reduced
+    pub fn from_bytes(bytes: &[u8], rows: usize, cols: usize, m: Modulus) -> Result<Self, CodecError> {
+        let data = unpack_slice(bytes, rows * cols, m.bits())?;
+
+        if let Some((index, &value)) = data.iter().enumerate().find(|(_, &x)| x >= m.q() as i32) {
+            return Err(CodecError::Coefficient { index, value });
+        }
+        Ok(Self { rows, cols, data })
+    }
+}
+
+// =====================
+// Public Matrix A from Seed
+// row i = SHAKE128(rho || i), 16-bit candidates masked
+// to ceil(log2 q) bits, reject >= q
+// =====================
+fn expand_a(rho: &PublicSeed, n: usize, m: Modulus) -> Matrix {
+    let mask = (1u32 << m.bits()) - 1;
+    let mut a = Matrix::zero(n, n);
+
+    for (i, row) in a.data.chunks_mut(n).enumerate() {
+        let mut xof = Shake128::default();
+        xof.update(rho);
+        xof.update(&(i as u16).to_le_bytes());
+        let mut reader = xof.finalize_xof();
+
+        let mut filled = 0;
+        let mut buf = [0u8; 2];
+        while filled < n {
+            reader.read(&mut buf);
+            let d = u16::from_le_bytes(buf) as u32 & mask;
+            if d < m.q() {
+                row[filled] = d as i32;
+                filled += 1;
+            }
+        }
+    }
+
+    a
+}
+
+// rows * cols samples, N per PRF nonce, starting at `nonce`
+fn noise_matrix(
+    dist: Dist,
+    seed: &NoiseSeed,
+    nonce: &mut u8,
+    rows: usize,
+    cols: usize,
+    m: Modulus,
+) -> Matrix {
+    let len = rows * cols;
+    let mut data = Vec::with_capacity(len);
+
+    while data.len() < len {
+        let p: Secret<Poly> = Secret::new(noise::sample(dist, seed, *nonce));
+        *nonce += 1;
+
+        let take = len - data.len();
+        data.extend(p.expose().coeffs.iter().take(take).map(|&c| m.lift(c)));
+    }
+
+    Matrix { rows, cols, data }
+}
+
+// =====================
+// Message Encoding
+// EXTRACTED_BITS per entry, scaled by q / 2^EXTRACTED_BITS
+// =====================
+fn encode_message(msg: &Message, m: Modulus) -> Matrix {
+    let b = EXTRACTED_BITS as usize;
+    let bit = |i: usize| ((msg[i / 8] >> (i % 8)) & 1) as u64;
+
+    let mut out = Matrix::zero(NBAR, NBAR);
+    for (i, x) in out.data.iter_mut().enumerate() {
+        let v: u64 = (0..b).map(|j| bit(i * b + j) << j).sum();
+        *x = ((v * m.q() as u64 + (1 << (b - 1))) >> b) as i32;
+    }
+    out
+}
+
+// round(x * 2^B / q) mod 2^B, division by Barrett so x never hits a divider
+fn decode_message(x: &Matrix, m: Modulus) -> Message {
+    let b = EXTRACTED_BITS as usize;
+
+    let mut msg: Message = [0u8; MSG_BYTES];
+    for (i, &c) in x.data.iter().enumerate() {
+        let (v, _) = m.divrem(((c as u64) << b) + m.q() as u64 / 2);
+        for j in 0..b {
+            let pos = i * b + j;
+            msg[pos / 8] |= (((v >> j) & 1) as u8) << (pos % 8);
+        }
+    }
+    msg
+}
+
+// =====================
+// Keys
+// A is expanded from seed on demand
+// =====================
+#[derive(Clone)]
+pub struct PublicKey {
+    pub seed: PublicSeed,
+    pub b: Matrix, // n x NBAR
+}
+
+impl PublicKey {
+    pub fn a(&self, params: &LweParams) -> Matrix {
+        expand_a(&self.seed, params.n, Modulus::new(params.q))
+    }
+
+    // seed || B
+    pub fn to_bytes(&self, params: &LweParams) -> Vec<u8> {
+        [&self.seed[..], &self.b.to_bytes(Modulus::new(params.q))].concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8], params: &LweParams) -> Result<Self, CodecError> {
+        let m = Modulus::new(params.q);
+        check_len(bytes, SEED_BYTES + Matrix::byte_len(params.n, NBAR, m))?;
+
+        let mut seed = [0u8; SEED_BYTES];
+        seed.copy_from_slice(&bytes[..SEED_BYTES]);
+        let b = Matrix::from_bytes(&bytes[SEED_BYTES..], params.n, NBAR, m)?;
+
+        Ok(Self { seed, b })
+    }
+}
+
+#[derive(Debug)]
+pub struct SecretKey {
+    s: Secret<Matrix>, // n x NBAR
+    pub h_pk: [u8; 32],
+}
+
+impl SecretKey {
+    // S || H(pk)
+    pub fn to_bytes(&self, params: &LweParams) -> Secret<Vec<u8>> {
+        let s = Secret::new(self.s.expose().to_bytes(Modulus::new(params.q)));
+        Secret::new([&s.expose()[..], &self.h_pk].concat())
+    }
+
+    pub fn from_bytes(bytes: &[u8], params: &LweParams) -> Result<Self, CodecError> {
+        let m = Modulus::new(params.q);
+        let s_len = Matrix::byte_len(params.n, NBAR, m);
+        check_len(bytes, s_len + 32)?;
+
+        let s = Secret::new(Matrix::from_bytes(&bytes[..s_len], params.n, NBAR, m)?);
+        let mut h_pk = [0u8; 32];
+        h_pk.copy_from_slice(&bytes[s_len..]);
+
+        Ok(Self { s, h_pk })
+    }
+}
+
+pub struct Ciphertext {
+    pub b: Matrix, // NBAR x n
+    pub c: Matrix, // NBAR x NBAR
+}
+
+impl Ciphertext {
+    // B' || C, uncompressed
+    pub fn to_bytes(&self, params: &LweParams) -> Vec<u8> {
+        let m = Modulus::new(params.q);
+        [self.b.to_bytes(m), self.c.to_bytes(m)].concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8], params: &LweParams) -> Result<Self, CodecError> {
+        let m = Modulus::new(params.q);
+        let b_len = Matrix::byte_len(NBAR, params.n, m);
+        check_len(bytes, b_len + Matrix::byte_len(NBAR, NBAR, m))?;
+
+        let b = Matrix::from_bytes(&bytes[..b_len], NBAR, params.n, m)?;
+        let c = Matrix::from_bytes(&bytes[b_len..], NBAR, NBAR, m)?;
+
+        Ok(Self { b, c })
+    }
+}
+
+// =====================
+// KeyGen
+// B = A S + E
+// =====================
+pub fn keygen(params: &LweParams) -> (PublicKey, SecretKey) {
+    keygen_with_rng(params, &mut rand::thread_rng())
+}
+
+pub fn keygen_with_rng(
+    params: &LweParams,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (PublicKey, SecretKey) {
+    let d = Secret::new(noise::fresh_seed(rng));
+    keygen_from_seed(d.expose(), params)
+}
+
+pub fn keygen_from_seed(d: &[u8; SEED_BYTES], params: &LweParams) -> (PublicKey, SecretKey) {
+    params.assert_supported();
+    let m = Modulus::new(params.q);
+
+    let (rho, sigma) = expand::split_seed(d);
+    let a = expand_a(&rho, params.n, m);
+
+    let mut nonce = 0;
+    let s = Secret::new(noise_matrix(params.secret, sigma.expose(), &mut nonce, params.n, NBAR, m));
+    let e = Secret::new(noise_matrix(params.noise, sigma.expose(), &mut nonce, params.n, NBAR, m));
+
+    let b = a.mul(s.expose(), m).add(e.expose(), m);
+
+    let pk = PublicKey { seed: rho, b };
+    let h_pk = kem::h(&pk.to_bytes(params));
+
+    (pk, SecretKey { s, h_pk })
+}
+
+// =====================
+// Encryption (deterministic in coins)
+// B' = S' A + E', C = S' B + E'' + encode(m)
+// =====================
+pub fn encrypt(pk: &PublicKey, msg: &Message, coins: &NoiseSeed, params: &LweParams) -> Ciphertext {
+    params.assert_supported();
+    let m = Modulus::new(params.q);
+
+    let mut nonce = 0;
+    let s1 = Secret::new(noise_matrix(params.secret, coins, &mut nonce, NBAR, params.n, m));
+    let e1 = Secret::new(noise_matrix(params.noise, coins, &mut nonce, NBAR, params.n, m));
+    let e2 = Secret::new(noise_matrix(params.noise, coins, &mut nonce, NBAR, NBAR, m));
+
+    let m_enc = Secret::new(encode_message(msg, m));
+
+    let b = s1.expose().mul(&pk.a(params), m).add(e1.expose(), m);
+    let v = Secret::new(s1.expose().mul(&pk.b, m).add(e2.expose(), m));
+    let c = v.expose().add(m_enc.expose(), m);
+
+    Ciphertext { b, c }
+}
+
+// C - B' S
+pub fn decrypt(ct: &Ciphertext, sk: &SecretKey, params: &LweParams) -> Message {
+    params.assert_supported();
+    let m = Modulus::new(params.q);
+
+    let bs = Secret::new(ct.b.mul(sk.s.expose(), m));
+    let m_enc = Secret::new(ct.c.sub(bs.expose(), m));
+
+    decode_message(m_enc.expose(), m)
+}
+
+// =====================
+// Encapsulation
+// =====================
+pub fn encaps(pk: &PublicKey, msg: &Message, params: &LweParams) -> (Ciphertext, SharedKey) {
+    encaps_with_rng(pk, msg, params, &mut rand::thread_rng())
+}
+
+pub fn encaps_with_rng(
+    pk: &PublicKey,
+    msg: &Message,
+    params: &LweParams,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (Ciphertext, SharedKey) {
+    let coins = Secret::new(noise::fresh_seed(rng));
+    let ct = encrypt(pk, msg, coins.expose(), params);
+
+    let key = kem::kdf(msg, &kem::h(&ct.to_bytes(params)), &kem::h(&pk.to_bytes(params)));
+    (ct, key)
+}
+
+// =====================
+// Decapsulation
+// =====================
+pub fn decaps(ct: &Ciphertext, sk: &SecretKey, params: &LweParams) -> SharedKey {
+    let m = Secret::new(decrypt(ct, sk, params));
+
+    kem::kdf(m.expose(), &kem::h(&ct.to_bytes(params)), &sk.h_pk)
+}
+
+// =====================
+// IND-CCA2 KEM (FO transform, see crypto::kem)
+// =====================
+pub struct Lwe {
+    pub params: LweParams,
+}
+
+impl Pke for Lwe {
+    type PublicKey = PublicKey;
+    type SecretKey = SecretKey;
+    type Ciphertext = Ciphertext;
+
+    fn keygen(&self, d: &[u8; SEED_BYTES]) -> (PublicKey, SecretKey) {
+        keygen_from_seed(d, &self.params)
+    }
+
+    fn encrypt(&self, pk: &PublicKey, m: &Message, coins: &NoiseSeed) -> Ciphertext {
+        encrypt(pk, m, coins, &self.params)
+    }
+
+    fn decrypt(&self, sk: &SecretKey, ct: &Ciphertext) -> Message {
+        decrypt(ct, sk, &self.params)
+    }
+
+    fn public_key_bytes(&self, pk: &PublicKey) -> Vec<u8> {
+        pk.to_bytes(&self.params)
+    }
+
+    fn secret_key_bytes(&self, sk: &SecretKey) -> Secret<Vec<u8>> {
+        sk.to_bytes(&self.params)
+    }
+
+    fn ciphertext_bytes(&self, ct: &Ciphertext) -> Vec<u8> {
+        ct.to_bytes(&self.params)
+    }
+}
@@ -0,0 +1,274 @@
+use rand::{CryptoRng, RngCore};
+
+use crate::crypto::codec::{check_len, CodecError};
+use crate::crypto::expand::{self, PublicSeed};
+use crate::crypto::kem::{self, Message, Pke, SharedKey};
+use crate::math::polyvec::{PolyMatrix, PolyVec};
+use crate::crypto::noise::{self, NoiseSeed, SEED_BYTES};
+use crate::crypto::params::{Dist, Params};
+use crate::crypto::secret::Secret;
+use crate::{decode_message, encode_message, SchemePoly};
+
+// =====================
+// Module-LWE (rank k over SchemePoly<N, Q>)
+// k = 2 / 3 / 4 targets the 512 / 768 / 1024 tiers
+// =====================
+pub const K512: usize = 2;
+pub const K768: usize = 3;
+pub const K1024: usize = 4;
+
+// A is expanded from seed on demand
+#[derive(Clone)]
+pub struct PublicKey<const K: usize, const N: usize, const Q: u32> {
+    pub seed: PublicSeed,
+    pub t: PolyVec<K, N, Q>,
+}
+
+impl<const K: usize, const N: usize, const Q: u32> PublicKey<K, N, Q> {
+    pub fn a(&self) -> PolyMatrix<K, N, Q> {
+        expand::expand_matrix(&self.seed)
+    }
+
+    // seed || t
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&self.seed[..], &self.t.to_bytes()].concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        check_len(bytes, SEED_BYTES + PolyVec::<K, N, Q>::BYTES)?;
+
+        let mut seed = [0u8; SEED_BYTES];
+        seed.copy_from_slice(&bytes[..SEED_BYTES]);
+        let t = PolyVec::from_bytes(&bytes[SEED_BYTES..])?;
+
+        Ok(Self { seed, t })
+    }
+}
+
+#[derive(Debug)]
+pub struct SecretKey<const K: usize, const N: usize, const Q: u32> {
+    s: Secret<PolyVec<K, N, Q>>,
+    pub h_pk: [u8; 32],
+}
+
+impl<const K: usize, const N: usize, const Q: u32> SecretKey<K, N, Q> {
+    // s || H(pk)
+    pub fn to_bytes(&self) -> Secret<Vec<u8>> {
+        let s = Secret::new(self.s.expose().to_bytes());
+        Secret::new([&s.expose()[..], &self.h_pk].concat())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        let s_len = PolyVec::<K, N, Q>::BYTES;
+        check_len(bytes, s_len + 32)?;
+
+        let s = Secret::new(PolyVec::from_bytes(&bytes[..s_len])?);
+        let mut h_pk = [0u8; 32];
+        h_pk.copy_from_slice(&bytes[s_len..]);
+
+        Ok(Self { s, h_pk })
+    }
+}
+
+pub struct Ciphertext<const K: usize, const N: usize, const Q: u32> {
+    pub u: PolyVec<K, N, Q>,
+    pub v: SchemePoly<N, Q>,
+}
+
+impl<const K: usize, const N: usize, const Q: u32> Ciphertext<K, N, Q> {
+    // compress_du(u) || compress_dv(v)
+    pub fn to_bytes(&self, params: &Params) -> Vec<u8> {
+        [
+            self.u.to_compressed_bytes(params.du),
+            self.v.to_compressed_bytes(params.dv),
+        ]
+        .concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8], params: &Params) -> Result<Self, CodecError> {
+        let u_len = PolyVec::<K, N, Q>::compressed_len(params.du);
+        check_len(bytes, u_len + SchemePoly::<N, Q>::compressed_len(params.dv))?;
+
+        let u = PolyVec::from_compressed_bytes(&bytes[..u_len], params.du)?;
+        let v = SchemePoly::from_compressed_bytes(&bytes[u_len..], params.dv)?;
+
+        Ok(Self { u, v })
+    }
+}
+
+// K independent samples with nonces first_nonce..first_nonce + K
+fn noise_vec<const K: usize, const N: usize, const Q: u32>(
+    dist: Dist,
+    seed: &NoiseSeed,
+    first_nonce: usize,
+) -> PolyVec<K, N, Q> {
+    PolyVec::from_fn(|i| noise::sample(dist, seed, (first_nonce + i) as u8))
+}
+
+// =====================
+// KeyGen
+// t = A s + e
+// =====================
+pub fn keygen<const K: usize, const N: usize, const Q: u32>(
+    params: &Params,
+) -> (PublicKey<K, N, Q>, SecretKey<K, N, Q>) {
+    keygen_with_rng(params, &mut rand::thread_rng())
+}
+
+pub fn keygen_with_rng<const K: usize, const N: usize, const Q: u32>(
+    params: &Params,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (PublicKey<K, N, Q>, SecretKey<K, N, Q>) {
+    let d = Secret::new(noise::fresh_seed(rng));
+    keygen_from_seed(d.expose(), params)
+}
+
+pub fn keygen_from_seed<const K: usize, const N: usize, const Q: u32>(
+    d: &[u8; SEED_BYTES],
+    params: &Params,
+) -> (PublicKey<K, N, Q>, SecretKey<K, N, Q>) {
+    params.assert_supported::<N, Q>(K);
+
+    let (rho, sigma) = expand::split_seed(d);
+    let a = expand::expand_matrix::<K, N, Q>(&rho);
+
+    let s = Secret::new(noise_vec(params.secret, sigma.expose(), 0));
+    let e = Secret::new(noise_vec(params.noise, sigma.expose(), K));
+
+    let t = a.mul_vec(s.expose()).add(e.expose());
+
+    let pk = PublicKey { seed: rho, t };
+    let h_pk = kem::h(&pk.to_bytes());
+
+    (pk, SecretKey { s, h_pk })
+}
+
+// =====================
+// Encryption (deterministic in coins)
+// u = A^T r + e1, v = t^T r + e2 + m
+// =====================
+pub fn encrypt<const K: usize, const N: usize, const Q: u32>(
+    pk: &PublicKey<K, N, Q>,
+    msg: &Message,
+    coins: &NoiseSeed,
+    params: &Params,
+) -> Ciphertext<K, N, Q> {
+    params.assert_supported::<N, Q>(K);
+
+    let m_poly = Secret::new(encode_message(msg));
+
+    let r = Secret::new(noise_vec(params.secret, coins, 0));
+    let e1 = Secret::new(noise_vec(params.noise, coins, K));
+    let e2 = Secret::new(noise::sample(params.noise, coins, (2 * K) as u8));
+
+    let u = pk.a().transpose_mul_vec(r.expose()).add(e1.expose());
+    let v = pk.t.dot(r.expose()) + e2.expose() + m_poly.expose();
+
+    Ciphertext {
+        u: u.compress_round_trip(params.du),
+        v: v.compress_round_trip(params.dv),
+    }
+}
+
+// v - s^T u
+pub fn decrypt<const K: usize, const N: usize, const Q: u32>(
+    ct: &Ciphertext<K, N, Q>,
+    sk: &SecretKey<K, N, Q>,
+    params: &Params,
+) -> Message {
+    params.assert_supported::<N, Q>(K);
+
+    let us = Secret::new(sk.s.expose().dot(&ct.u));
+    let m_poly = Secret::new(&ct.v - us.expose());
+
+    decode_message(m_poly.expose())
+}
+
+// v - s^T u - m: the error decrypt rounds away (see crypto::failure)
+pub fn noise_term<const K: usize, const N: usize, const Q: u32>(
+    ct: &Ciphertext<K, N, Q>,
+    sk: &SecretKey<K, N, Q>,
+    msg: &Message,
+    params: &Params,
+) -> Secret<SchemePoly<N, Q>> {
+    params.assert_supported::<N, Q>(K);
+
+    let us = Secret::new(sk.s.expose().dot(&ct.u));
+    let m_poly = Secret::new(encode_message(msg));
+
+    Secret::new(&ct.v - us.expose() - m_poly.expose())
+}
+
+// =====================
+// Encapsulation
+// =====================
+pub fn encaps<const K: usize, const N: usize, const Q: u32>(
+    pk: &PublicKey<K, N, Q>,
+    msg: &Message,
+    params: &Params,
+) -> (Ciphertext<K, N, Q>, SharedKey) {
+    encaps_with_rng(pk, msg, params, &mut rand::thread_rng())
+}
+
+pub fn encaps_with_rng<const K: usize, const N: usize, const Q: u32>(
+    pk: &PublicKey<K, N, Q>,
+    msg: &Message,
+    params: &Params,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (Ciphertext<K, N, Q>, SharedKey) {
+    let coins = Secret::new(noise::fresh_seed(rng));
+    let ct = encrypt(pk, msg, coins.expose(), params);
+
+    let key = kem::kdf(msg, &kem::h(&ct.to_bytes(params)), &kem::h(&pk.to_bytes()));
+    (ct, key)
+}
+
+// =====================
+// Decapsulation
+// =====================
+pub fn decaps<const K: usize, const N: usize, const Q: u32>(
+    ct: &Ciphertext<K, N, Q>,
+    sk: &SecretKey<K, N, Q>,
+    params: &Params,
+) -> SharedKey {
+    let m = Secret::new(decrypt(ct, sk, params));
+
+    kem::kdf(m.expose(), &kem::h(&ct.to_bytes(params)), &sk.h_pk)
+}
+
+// =====================
+// IND-CCA2 KEM (FO transform, see crypto::kem)
+// =====================
+pub struct Mlwe<const K: usize, const N: usize, const Q: u32> {
+    pub params: Params,
+}
+
+impl<const K: usize, const N: usize, const Q: u32> Pke for Mlwe<K, N, Q> {
+    type PublicKey = PublicKey<K, N, Q>;
+    type SecretKey = SecretKey<K, N, Q>;
+    type Ciphertext = Ciphertext<K, N, Q>;
+
+    fn keygen(&self, d: &[u8; SEED_BYTES]) -> (PublicKey<K, N, Q>, SecretKey<K, N, Q>) {
+        keygen_from_seed(d, &self.params)
+    }
+
+    fn encrypt(&self, pk: &PublicKey<K, N, Q>, m: &Message, coins: &NoiseSeed) -> Ciphertext<K, N, Q> {
+        encrypt(pk, m, coins, &self.params)
+    }
+
+    fn decrypt(&self, sk: &SecretKey<K, N, Q>, ct: &Ciphertext<K, N, Q>) -> Message {
+        decrypt(ct, sk, &self.params)
+    }
+
+    fn public_key_bytes(&self, pk: &PublicKey<K, N, Q>) -> Vec<u8> {
+        pk.to_bytes()
+    }
+
+    fn secret_key_bytes(&self, sk: &SecretKey<K, N, Q>) -> Secret<Vec<u8>> {
+        sk.to_bytes()
+    }
+
+    fn ciphertext_bytes(&self, ct: &Ciphertext<K, N, Q>) -> Vec<u8> {
+        ct.to_bytes(&self.params)
+    }
+}
@@ -0,0 +1,12 @@
+pub mod codec;
+pub mod drbg;
+pub mod expand;
+pub mod failure;
+pub mod kat;
+pub mod kem;
+pub mod lwe;
+pub mod mlwe;
+pub mod noise;
+pub mod ntru_prime;
+pub mod params;
+pub mod secret;
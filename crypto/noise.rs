@@ -0,0 +1,83 @@
+use rand::{CryptoRng, RngCore};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+use crate::crypto::params::Dist;
+use crate::SchemePoly;
+
+pub const SEED_BYTES: usize = 32;
+
+pub type NoiseSeed = [u8; SEED_BYTES];
+
+pub fn fresh_seed(rng: &mut (impl RngCore + CryptoRng)) -> NoiseSeed {
+    let mut seed = [0u8; SEED_BYTES];
+    rng.fill_bytes(&mut seed);
+    seed
+}
+
+// =====================
+// PRF: SHAKE256(seed || nonce)
+// =====================
+pub fn prf(seed: &NoiseSeed, nonce: u8) -> impl XofReader {
+    let mut xof = Shake256::default();
+    xof.update(seed);
+    xof.update(&[nonce]);
+    xof.finalize_xof()
+}
+
+// =====================
+// Centered Binomial CBD(eta)
+// 2 * eta bits per coefficient: sum of eta bits minus sum of eta bits
+// =====================
+pub fn cbd<const N: usize, const Q: u32>(eta: u32, buf: &[u8]) -> SchemePoly<N, Q> {
+    let eta = eta as usize;
+    assert_eq!(buf.len(), 2 * eta * N / 8, "cbd: wrong buffer length");
+
+    let bit = |i: usize| ((buf[i / 8] >> (i % 8)) & 1) as i32;
+
+    let mut p = SchemePoly::zero();
+    for (i, c) in p.coeffs.iter_mut().enumerate() {
+        let base = 2 * eta * i;
+        let a: i32 = (0..eta).map(|j| bit(base + j)).sum();
+        let b: i32 = (0..eta).map(|j| bit(base + eta + j)).sum();
+        *c = a - b;
+    }
+    p
+}
+
+// uniform [-w, w] by rejection, so the PRF output carries no modulo bias
+fn uniform<const N: usize, const Q: u32>(w: i32, xof: &mut impl XofReader) -> SchemePoly<N, Q> {
+    let m = (2 * w + 1) as u32;
+    let limit = 256 - 256 % m;
+
+    let mut p = SchemePoly::zero();
+    let mut byte = [0u8; 1];
+    for c in p.coeffs.iter_mut() {
+        loop {
+            xof.read(&mut byte);
+            if (byte[0] as u32) < limit {
+                *c = (byte[0] as u32 % m) as i32 - w;
+                break;
+            }
+        }
+    }
+    p
+}
+
+// =====================
+// Sample Dist from (seed, nonce)
+// every (seed, nonce) pair yields an independent polynomial
+// =====================
+pub fn sample<const N: usize, const Q: u32>(dist: Dist, seed: &NoiseSeed, nonce: u8) -> SchemePoly<N, Q> {
+    let mut xof = prf(seed, nonce);
+
+    match dist {
+        Dist::Cbd(eta) => {
+            let mut buf = vec![0u8; 2 * eta as usize * N / 8];
+            xof.read(&mut buf);
+            cbd(eta, &buf)
+        }
+        Dist::Ternary => uniform(1, &mut xof),
+        Dist::Uniform(w) => uniform(w, &mut xof),
+    }
+}
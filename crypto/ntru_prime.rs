@@ -0,0 +1,502 @@
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+
+use crate::crypto::codec::{check_len, CodecError};
+use crate::crypto::kem::SharedKey;
+use crate::crypto::secret::Secret;
+use crate::math::ct::{ct_eq, ct_select, ct_select_i32};
+use crate::math::poly::Poly;
+use crate::math::ring::NtruPrime;
+use crate::math::sparse::SparseTernary;
+
+// =====================
+// Streamlined NTRU Prime (sntrup761)
+// R = Z[x] / (x^p - x - 1) with p prime, so R/q is a field.
+// Short polys have exactly w coefficients +-1 and the rest 0.
+// pk h = g / (3f) in R/q, ciphertext c = Round(h·r); then
+// 3f·c = g·r (mod 3) and r comes back through 1/g in R/3
+// =====================
+pub const P: usize = 761;
+pub const Q: u32 = 4591;
+pub const W: usize = 286;
+
+pub type Rq = Poly<P, Q, NtruPrime>;
+pub type R3 = Poly<P, 3, NtruPrime>;
+
+const HALF_Q: i32 = (Q as i32 - 1) / 2;
+const ROUNDED_M: u32 = Q.div_ceil(3); // values of a multiple of 3 in (-q/2, q/2)
+
+pub const HASH_BYTES: usize = 32;
+pub const SMALL_BYTES: usize = P.div_ceil(4);
+pub const RQ_BYTES: usize = encoded_len(Q, P);
+pub const ROUNDED_BYTES: usize = encoded_len(ROUNDED_M, P);
+
+pub const PUBLIC_KEY_BYTES: usize = RQ_BYTES;
+pub const SECRET_KEY_BYTES: usize = 2 * SMALL_BYTES + PUBLIC_KEY_BYTES + SMALL_BYTES + HASH_BYTES;
+pub const CIPHERTEXT_BYTES: usize = ROUNDED_BYTES + HASH_BYTES;
+
+// =====================
+// Samplers
+// 32-bit little-endian words from the rng, as in the reference code
+// =====================
+fn random_words(rng: &mut (impl RngCore + CryptoRng)) -> Secret<Vec<u32>> {
+    let mut buf = vec![0u8; 4 * P];
+    rng.fill_bytes(&mut buf);
+    let buf = Secret::new(buf);
+
+    Secret::new(
+        buf.expose()
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect(),
+    )
+}
+
+// uniform {-1, 0, 1}: ((x mod 2^30) * 3 >> 30) - 1
+pub fn small_random(rng: &mut (impl RngCore + CryptoRng)) -> R3 {
+    let words = random_words(rng);
+    let mut a = R3::zero();
+    for (c, &x) in a.coeffs.iter_mut().zip(words.expose().iter()) {
+        *c = (((x & 0x3fff_ffff) as u64 * 3) >> 30) as i32 - 1;
+    }
+    a
+}
+
+// exactly w coefficients +-1 at uniformly random positions: the
+// reference code's sort, through SparseTernary::fixed_weight
+pub fn short_random(rng: &mut (impl RngCore + CryptoRng)) -> R3 {
+    let words = random_words(rng);
+    let s = Secret::new(SparseTernary::<P>::fixed_weight(W, words.expose()));
+    s.expose().to_poly()
+}
+
+// small coefficients as R/q elements
+fn lift(a: &R3) -> Rq {
+    Rq::from_coeffs(a.to_centered().coeffs)
+}
+
+// each coefficient to the nearest multiple of 3
+fn round(a: &Rq) -> Rq {
+    let a = a.to_centered();
+    let r = R3::from_coeffs(a.coeffs).to_centered();
+    let mut out = Rq::zero();
+    for ((o, &x), &y) in out.coeffs.iter_mut().zip(a.coeffs.iter()).zip(r.coeffs.iter()) {
+        *o = x - y;
+    }
+    out
+}
+
+// =====================
+// Encode / Decode
+// the reference mixed-radix encoding: neighbours are merged
+// while their range stays under 2^14 and full bytes are
+// emitted along the way, so R/q costs ~log2(q) bits per entry
+// =====================
+fn encode(r: &[u32], m: &[u32]) -> Vec<u8> {
+    let mut out = Vec::new();
+    if m.is_empty() {
+        return out;
+    }
+
+    if m.len() == 1 {
+        let (mut r, mut m) = (r[0], m[0]);
+        while m > 1 {
+            out.push(r as u8);
+            r >>= 8;
+            m = (m + 255) >> 8;
+        }
+        return out;
+    }
+
+    let mut r2 = Vec::with_capacity(m.len().div_ceil(2));
+    let mut m2 = Vec::with_capacity(m.len().div_ceil(2));
+    for (r, m) in r.chunks_exact(2).zip(m.chunks_exact(2)) {
+        let (mut x, mut mm) = (r[0] + m[0] * r[1], m[0] * m[1]);
+        while mm >= 16384 {
+            out.push(x as u8);
+            x >>= 8;
+            mm = (mm + 255) >> 8;
+        }
+        r2.push(x);
+        m2.push(mm);
+    }
+    if m.len() % 2 == 1 {
+        r2.push(r[m.len() - 1]);
+        m2.push(m[m.len() - 1]);
+    }
+
+    out.extend(encode(&r2, &m2));
+    out
+}
+
+// total on well-sized input: every value comes back reduced mod its m
+fn decode(s: &[u8], m: &[u32]) -> Vec<u32> {
+    if m.is_empty() {
+        return Vec::new();
+    }
+
+    if m.len() == 1 {
+        let x = s.iter().rev().fold(0u32, |acc, &b| (acc << 8) | b as u32);
+        return vec![x % m[0]];
+    }
+
+    let mut k = 0;
+    let mut bottom = Vec::with_capacity(m.len() / 2);
+    let mut m2 = Vec::with_capacity(m.len().div_ceil(2));
+    for m in m.chunks_exact(2) {
+        let (mut mm, mut r, mut t) = (m[0] * m[1], 0u32, 1u32);
+        while mm >= 16384 {
+            r += s[k] as u32 * t;
+            t <<= 8;
+            k += 1;
+            mm = (mm + 255) >> 8;
+        }
+        bottom.push((r, t));
+        m2.push(mm);
+    }
+    if m.len() % 2 == 1 {
+        m2.push(m[m.len() - 1]);
+    }
+
+    let r2 = decode(&s[k..], &m2);
+
+    let mut out = Vec::with_capacity(m.len());
+    for ((&(r, t), m), &top) in bottom.iter().zip(m.chunks_exact(2)).zip(r2.iter()) {
+        let x = r.wrapping_add(t.wrapping_mul(top));
+        out.push(x % m[0]);
+        out.push((x / m[0]) % m[1]);
+    }
+    if m.len() % 2 == 1 {
+        out.push(r2[r2.len() - 1]);
+    }
+    out
+}
+
+// bytes encode() writes for `len` values that all lie in [0, m)
+const fn encoded_len(m: u32, len: usize) -> usize {
+    // after each round every entry shares one range except the last
+    let (mut m, mut last, mut len, mut bytes) = (m as u64, m as u64, len, 0);
+
+    while len > 1 {
+        let (mut pair, mut pair_bytes) = (m * m, 0);
+        while pair >= 16384 {
+            pair = (pair + 255) >> 8;
+            pair_bytes += 1;
+        }
+
+        if len % 2 == 0 {
+            let (mut tail, mut tail_bytes) = (m * last, 0);
+            while tail >= 16384 {
+                tail = (tail + 255) >> 8;
+                tail_bytes += 1;
+            }
+            bytes += (len / 2 - 1) * pair_bytes + tail_bytes;
+            last = tail;
+        } else {
+            bytes += (len / 2) * pair_bytes;
+        }
+
+        m = pair;
+        len = len.div_ceil(2);
+    }
+
+    while last > 1 {
+        last = (last + 255) >> 8;
+        bytes += 1;
+    }
+    bytes
+}
+
+// canonical only: anything else would give one ciphertext two encodings
+fn decode_canonical(
+    bytes: &[u8],
+    expected: usize,
+    m: u32,
+    decoded: impl Fn(u32) -> i32,
+    encoded: impl Fn(&Rq) -> Vec<u8>,
+) -> Result<Rq, CodecError> {
+    check_len(bytes, expected)?;
+
+    let mut a = Rq::zero();
+    for (c, x) in a.coeffs.iter_mut().zip(decode(bytes, &[m; P])) {
+        *c = decoded(x);
+    }
+
+    if encoded(&a) != bytes {
+        return Err(CodecError::NonCanonical);
+    }
+    Ok(a)
+}
+
+// coefficients in (-q/2, q/2), shifted to [0, q)
+fn rq_encode(a: &Rq) -> Vec<u8> {
+    let r: Vec<u32> = a.to_centered().coeffs.iter().map(|&c| (c + HALF_Q) as u32).collect();
+    encode(&r, &[Q; P])
+}
+
+fn rq_decode(bytes: &[u8]) -> Result<Rq, CodecError> {
+    decode_canonical(bytes, RQ_BYTES, Q, |x| x as i32 - HALF_Q, rq_encode)
+}
+
+// multiples of 3 only, stored divided by 3 (x * 10923 >> 15 = x / 3 here)
+fn rounded_encode(a: &Rq) -> Vec<u8> {
+    let r: Vec<u32> = a
+        .to_centered()
+        .coeffs
+        .iter()
+        .map(|&c| ((c + HALF_Q) as u32 * 10923) >> 15)
+        .collect();
+    encode(&r, &[ROUNDED_M; P])
+}
+
+fn rounded_decode(bytes: &[u8]) -> Result<Rq, CodecError> {
+    decode_canonical(bytes, ROUNDED_BYTES, ROUNDED_M, |x| 3 * x as i32 - HALF_Q, rounded_encode)
+}
+
+// 2 bits per coefficient, c + 1, four to a byte
+fn small_encode(a: &R3) -> [u8; SMALL_BYTES] {
+    let a = a.to_centered();
+    let mut out = [0u8; SMALL_BYTES];
+    for (o, chunk) in out.iter_mut().zip(a.coeffs.chunks(4)) {
+        *o = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (j, &c)| acc | (((c + 1) as u8) << (2 * j)));
+    }
+    out
+}
+
+fn small_decode(bytes: &[u8]) -> Result<R3, CodecError> {
+    check_len(bytes, SMALL_BYTES)?;
+
+    let mut a = R3::zero();
+    for (i, c) in a.coeffs.iter_mut().enumerate() {
+        *c = ((bytes[i / 4] >> (2 * (i % 4))) & 3) as i32 - 1;
+        if *c > 1 {
+            return Err(CodecError::Coefficient { index: i, value: *c });
+        }
+    }
+
+    // unused high bits of the last byte
+    let used = P - 4 * (SMALL_BYTES - 1);
+    if used < 4 && bytes[SMALL_BYTES - 1] >> (2 * used) != 0 {
+        return Err(CodecError::NonCanonical);
+    }
+    Ok(a)
+}
+
+// =====================
+// Hash Functions
+// Hash_b(x) = SHA-512(b || x) truncated to 32 bytes; the prefix
+// b separates the uses: 4 = pk cache, 2 / 3 = confirmation,
+// 1 / 0 = session key after success / implicit rejection
+// =====================
+fn hash_prefix(b: u8, parts: &[&[u8]]) -> [u8; HASH_BYTES] {
+    let mut hasher = Sha512::new().chain_update([b]);
+    for part in parts {
+        hasher.update(part);
+    }
+
+    let mut out = [0u8; HASH_BYTES];
+    out.copy_from_slice(&hasher.finalize()[..HASH_BYTES]);
+    out
+}
+
+fn hash_confirm(r_enc: &[u8], cache: &[u8; HASH_BYTES]) -> [u8; HASH_BYTES] {
+    hash_prefix(2, &[&hash_prefix(3, &[r_enc]), cache])
+}
+
+fn hash_session(b: u8, y: &[u8], ct: &[u8]) -> SharedKey {
+    hash_prefix(b, &[&hash_prefix(3, &[y]), ct])
+}
+
+// =====================
+// Keys
+// =====================
+#[derive(Clone, Debug)]
+pub struct PublicKey {
+    pub h: Rq,
+}
+
+impl PublicKey {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        rq_encode(&self.h)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        Ok(Self { h: rq_decode(bytes)? })
+    }
+}
+
+#[derive(Debug)]
+pub struct SecretKey {
+    f: Secret<R3>,
+    ginv: Secret<R3>, // 1 / g in R/3
+    pub pk: PublicKey,
+    rho: Secret<[u8; SMALL_BYTES]>, // implicit-rejection input
+    pub cache: [u8; HASH_BYTES],    // Hash_4(pk)
+}
+
+impl SecretKey {
+    // f || 1/g || pk || rho || Hash_4(pk)
+    pub fn to_bytes(&self) -> Secret<Vec<u8>> {
+        let f = Secret::new(small_encode(self.f.expose()));
+        let ginv = Secret::new(small_encode(self.ginv.expose()));
+        Secret::new(
+            [
+                &f.expose()[..],
+                &ginv.expose()[..],
+                &self.pk.to_bytes(),
+                self.rho.expose(),
+                &self.cache,
+            ]
+            .concat(),
+        )
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        check_len(bytes, SECRET_KEY_BYTES)?;
+        let (f, rest) = bytes.split_at(SMALL_BYTES);
+        let (ginv, rest) = rest.split_at(SMALL_BYTES);
+        let (pk, rest) = rest.split_at(PUBLIC_KEY_BYTES);
+        let (rho, cache) = rest.split_at(SMALL_BYTES);
+
+        let mut rho_bytes = [0u8; SMALL_BYTES];
+        let mut cache_bytes = [0u8; HASH_BYTES];
+        rho_bytes.copy_from_slice(rho);
+        cache_bytes.copy_from_slice(cache);
+
+        Ok(Self {
+            f: Secret::new(small_decode(f)?),
+            ginv: Secret::new(small_decode(ginv)?),
+            pk: PublicKey::from_bytes(pk)?,
+            rho: Secret::new(rho_bytes),
+            cache: cache_bytes,
+        })
+    }
+}
+
+pub struct Ciphertext {
+    pub c: Rq, // rounded: every coefficient a multiple of 3
+    pub confirm: [u8; HASH_BYTES],
+}
+
+impl Ciphertext {
+    // Rounded(c) || confirm
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&rounded_encode(&self.c)[..], &self.confirm].concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        check_len(bytes, CIPHERTEXT_BYTES)?;
+        let (c, confirm) = bytes.split_at(ROUNDED_BYTES);
+
+        let mut confirm_bytes = [0u8; HASH_BYTES];
+        confirm_bytes.copy_from_slice(confirm);
+
+        Ok(Self { c: rounded_decode(c)?, confirm: confirm_bytes })
+    }
+}
+
+// =====================
+// KeyGen
+// g small and invertible mod 3, f short, h = g / (3f)
+// =====================
+pub fn keygen() -> (PublicKey, SecretKey) {
+    keygen_with_rng(&mut rand::thread_rng())
+}
+
+pub fn keygen_with_rng(rng: &mut (impl RngCore + CryptoRng)) -> (PublicKey, SecretKey) {
+    let (g, ginv) = loop {
+        let g = Secret::new(small_random(rng));
+        if let Some(ginv) = g.expose().inverse() {
+            break (g, Secret::new(ginv));
+        }
+    };
+
+    // R/q is a field, so 3f is always invertible
+    let f = Secret::new(short_random(rng));
+    let f3_inv = Secret::new((lift(f.expose()) * 3).inverse().expect("3f invertible in R/q"));
+    let h = f3_inv.expose() * lift(g.expose());
+
+    let pk = PublicKey { h };
+    let cache = hash_prefix(4, &[&pk.to_bytes()]);
+
+    let mut rho = [0u8; SMALL_BYTES];
+    rng.fill_bytes(&mut rho);
+    let rho = Secret::new(rho);
+
+    (pk.clone(), SecretKey { f, ginv, pk, rho, cache })
+}
+
+// =====================
+// Encryption (deterministic in r)
+// c = Round(h·r)
+// =====================
+pub fn encrypt(r: &R3, pk: &PublicKey) -> Rq {
+    round(&(&pk.h * lift(r)))
+}
+
+// e = 3f·c mod 3 = g·r, then r = e / g; anything that is not
+// weight w becomes the fixed short (1, ..., 1, 0, ..., 0)
+pub fn decrypt(c: &Rq, sk: &SecretKey) -> R3 {
+    let cf3 = Secret::new(c * lift(sk.f.expose()) * 3);
+    let e = Secret::new(R3::from_coeffs(cf3.expose().to_centered().coeffs));
+    let ev = Secret::new((e.expose() * sk.ginv.expose()).to_centered());
+
+    let weight: i32 = ev.expose().coeffs.iter().map(|&c| c & 1).sum();
+    let d = weight - W as i32;
+    let bad = (d | -d) >> 31; // all ones iff weight != w
+
+    let mut r = R3::zero();
+    for (i, (o, &c)) in r.coeffs.iter_mut().zip(ev.expose().coeffs.iter()).enumerate() {
+        *o = ct_select_i32(bad, (i < W) as i32, c);
+    }
+    r
+}
+
+// =====================
+// Encapsulation
+// ct = Rounded(c) || Hash_2(Hash_3(r) || Hash_4(pk))
+// K  = Hash_1(Hash_3(r) || ct)
+// =====================
+pub fn encaps(pk: &PublicKey) -> (Ciphertext, SharedKey) {
+    encaps_with_rng(pk, &mut rand::thread_rng())
+}
+
+pub fn encaps_with_rng(
+    pk: &PublicKey,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (Ciphertext, SharedKey) {
+    let r = Secret::new(short_random(rng));
+    let r_enc = Secret::new(small_encode(r.expose()));
+    let cache = hash_prefix(4, &[&pk.to_bytes()]);
+
+    let ct = Ciphertext {
+        c: encrypt(r.expose(), pk),
+        confirm: hash_confirm(r_enc.expose(), &cache),
+    };
+    let key = hash_session(1, r_enc.expose(), &ct.to_bytes());
+
+    (ct, key)
+}
+
+// =====================
+// Decapsulation
+// re-encrypt and compare; on mismatch K = Hash_0(Hash_3(rho) || ct)
+// =====================
+pub fn decaps(ct: &Ciphertext, sk: &SecretKey) -> SharedKey {
+    let r = Secret::new(decrypt(&ct.c, sk));
+    let r_enc = Secret::new(small_encode(r.expose()));
+
+    let ct_bytes = ct.to_bytes();
+    let ct_check = Ciphertext {
+        c: encrypt(r.expose(), &sk.pk),
+        confirm: hash_confirm(r_enc.expose(), &sk.cache),
+    }
+    .to_bytes();
+
+    let ok = ct_eq(&ct_bytes, &ct_check);
+    let input = Secret::new(ct_select(ok, r_enc.expose(), sk.rho.expose()));
+    hash_session(ok & 1, input.expose(), &ct_bytes)
+}
@@ -0,0 +1,184 @@
+use rand::Rng;
+
+use crate::crypto::kem::MSG_BYTES;
+use crate::math::modulus::Modulus;
+use crate::{N, Q};
+
+// =====================
+// Coefficient Distributions
+// =====================
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dist {
+    Ternary,      // uniform {-1, 0, 1}
+    Uniform(i32), // uniform [-w, w]
+    Cbd(u32),     // centered binomial, eta <= 4
+}
+
+impl Dist {
+    pub fn sample(self, rng: &mut impl Rng) -> i32 {
+        match self {
+            Dist::Ternary => rng.gen_range(-1..=1),
+            Dist::Uniform(w) => rng.gen_range(-w..=w),
+            Dist::Cbd(eta) => {
+                let a: i32 = (0..eta).map(|_| rng.gen::<bool>() as i32).sum();
+                let b: i32 = (0..eta).map(|_| rng.gen::<bool>() as i32).sum();
+                a - b
+            }
+        }
+    }
+
+    fn is_valid(self, q: u32) -> bool {
+        match self {
+            Dist::Ternary => true,
+            Dist::Uniform(w) => (0..q as i32 / 4).contains(&w),
+            Dist::Cbd(eta) => (1..=4).contains(&eta),
+        }
+    }
+}
+
+// =====================
+// Parameter Set
+// the schemes are generic over the ring degree and modulus
+// (crate::SchemePoly<N, Q>); a set runs on the instantiation
+// whose N and Q match its n and q
+// =====================
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Params {
+    pub name: &'static str,
+    pub n: usize,  // ring degree
+    pub q: u32,    // modulus
+    pub k: usize,  // module rank, 1 = plain ring
+    pub secret: Dist,
+    pub noise: Dist,
+    pub du: u32,   // compression bits for u
+    pub dv: u32,   // compression bits for v
+}
+
+impl Params {
+    // n >= 256 leaves room for one message bit per coefficient and keeps
+    // CBD on whole bytes, q < 2^16 keeps compression inside a u32
+    pub fn assert_valid(&self) {
+        let bits = Modulus::new(self.q).bits();
+
+        assert!(self.n >= MSG_BYTES * 8 && self.n.is_multiple_of(4), "{}: bad ring degree {}", self.name, self.n);
+        assert!((257..1 << 16).contains(&self.q), "{}: modulus must be in 257..2^16", self.name);
+        assert!(self.secret.is_valid(self.q), "{}: bad secret distribution", self.name);
+        assert!(self.noise.is_valid(self.q), "{}: bad noise distribution", self.name);
+        assert!(
+            (1..=bits).contains(&self.du) && (1..=bits).contains(&self.dv),
+            "{}: compression bits must be in 1..={}",
+            self.name,
+            bits
+        );
+    }
+
+    pub fn assert_supported<const N: usize, const Q: u32>(&self, k: usize) {
+        self.assert_valid();
+        assert_eq!((self.n, self.q), (N, Q), "{}: parameter set is n = {}, q = {}", self.name, self.n, self.q);
+        assert_eq!(self.k, k, "{}: parameter set is rank {}", self.name, self.k);
+    }
+}
+
+// =====================
+// Presets
+// =====================
+
+// the original demo: ternary secret, [-2, 2] noise, no compression
+pub const TOY: Params = Params {
+    name: "pqc-core-toy",
+    n: N,
+    q: Q as u32,
+    k: 1,
+    secret: Dist::Ternary,
+    noise: Dist::Uniform(2),
+    du: 12,
+    dv: 12,
+};
+
+pub const MLWE_512: Params = Params {
+    name: "mlwe-512",
+    n: N,
+    q: Q as u32,
+    k: 2,
+    secret: Dist::Cbd(3),
+    noise: Dist::Cbd(2),
+    du: 10,
+    dv: 4,
+};
+
+pub const MLWE_768: Params = Params {
+    name: "mlwe-768",
+    n: N,
+    q: Q as u32,
+    k: 3,
+    secret: Dist::Cbd(2),
+    noise: Dist::Cbd(2),
+    du: 10,
+    dv: 4,
+};
+
+pub const MLWE_1024: Params = Params {
+    name: "mlwe-1024",
+    n: N,
+    q: Q as u32,
+    k: 4,
+    secret: Dist::Cbd(2),
+    noise: Dist::Cbd(2),
+    du: 11,
+    dv: 5,
+};
+
+// NewHope-512 degree and modulus: a larger ring, q = 12289 (14 bits)
+// instead of 3329, u sent uncompressed
+pub const RLWE_512: Params = Params {
+    name: "rlwe-512",
+    n: 512,
+    q: 12289,
+    k: 1,
+    secret: Dist::Cbd(4),
+    noise: Dist::Cbd(4),
+    du: 14,
+    dv: 4,
+};
+
+// =====================
+// Plain LWE Parameter Set (crypto::lwe)
+// unstructured n x n matrix, so n and q are free here
+// =====================
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LweParams {
+    pub name: &'static str,
+    pub n: usize, // LWE dimension
+    pub q: u32,   // modulus, power of two or not
+    pub secret: Dist,
+    pub noise: Dist,
+}
+
+impl LweParams {
+    // q <= 2^16 keeps every product in a u64 accumulator,
+    // n <= 2048 keeps the noise nonces within a u8
+    pub fn assert_supported(&self) {
+        assert!((1..=2048).contains(&self.n), "{}: dimension must be in 1..=2048", self.name);
+        assert!((256..=1 << 16).contains(&self.q), "{}: modulus must be in 2^8..=2^16", self.name);
+        assert!(self.secret.is_valid(self.q), "{}: bad secret distribution", self.name);
+        assert!(self.noise.is_valid(self.q), "{}: bad noise distribution", self.name);
+    }
+}
+
+// Frodo-640 shape with q = 2^15
+pub const LWE_640: LweParams = LweParams {
+    name: "lwe-640",
+    n: 640,
+    q: 1 << 15,
+    secret: Dist::Cbd(4),
+    noise: Dist::Cbd(4),
+};
+
+// same, with the largest prime below 2^15
+pub const LWE_640_PRIME: LweParams = LweParams {
+    name: "lwe-640-prime",
+    n: 640,
+    q: 32749,
+    secret: Dist::Cbd(4),
+    noise: Dist::Cbd(4),
+};
@@ -0,0 +1,67 @@
+use std::fmt;
+
+use zeroize::Zeroize;
+
+use crate::crypto::lwe::Matrix;
+use crate::math::poly::Poly;
+use crate::math::polyvec::PolyVec;
+use crate::math::ring::Ring;
+use crate::math::sparse::SparseTernary;
+
+// =====================
+// Secret Wrapper
+// wiped on drop, redacted in Debug, never Clone;
+// the value is only reachable through `expose`
+// =====================
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl<const N: usize, const Q: u32, R: Ring> Zeroize for Poly<N, Q, R> {
+    fn zeroize(&mut self) {
+        self.coeffs.zeroize();
+    }
+}
+
+impl<const K: usize, const N: usize, const Q: u32> Zeroize for PolyVec<K, N, Q> {
+    fn zeroize(&mut self) {
+        for p in self.polys.iter_mut() {
+            p.zeroize();
+        }
+    }
+}
+
+impl Zeroize for Matrix {
+    fn zeroize(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl<const N: usize> Zeroize for SparseTernary<N> {
+    fn zeroize(&mut self) {
+        for (i, s) in self.terms.iter_mut() {
+            i.zeroize();
+            s.zeroize();
+        }
+    }
+}
@@ -2,6 +2,15 @@ use sha2::{Sha256, Digest};
 use oqs::sig::{Sig, Algorithm};
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use zeroize::Zeroize;
+use rand::{CryptoRng, RngCore};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 // ================= ERROR =================
 
@@ -18,6 +27,92 @@ pub enum ChainError {
 
     #[error("Serialize gagal")]
     Serialize,
+
+    #[error("DRBG KAT gagal")]
+    KatDrbg,
+
+    #[error("File KAT gagal dibaca/ditulis")]
+    KatIo,
+
+    #[error("File KAT rusak di baris {0}")]
+    KatParse(usize),
+
+    #[error("KAT tidak cocok: count {count}, {field}")]
+    KatMismatch { count: usize, field: String },
+
+    #[error("File block gagal dibaca/ditulis")]
+    StoreIo,
+
+    #[error("File bukan block store")]
+    StoreFormat,
+
+    #[error("Block {0} tidak valid")]
+    InvalidBlock(usize),
+}
+
+// ================= SECRET =================
+
+// dihapus saat drop, tidak bisa di-Clone, Debug disensor
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    pub fn expose(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretBytes([REDACTED])")
+    }
+}
+
+// ================= RNG =================
+
+// liboqs cuma punya satu callback randombytes global tanpa konteks,
+// jadi RNG yang disuntikkan dititipkan per-thread selama keypair berjalan
+thread_local! {
+    static INJECTED_RNG: RefCell<Option<*mut dyn RngCore>> = const { RefCell::new(None) };
+}
+
+unsafe extern "C" fn oqs_randombytes(buf: *mut u8, len: usize) {
+    let out = std::slice::from_raw_parts_mut(buf, len);
+    INJECTED_RNG.with(|slot| match *slot.borrow() {
+        Some(rng) => (*rng).fill_bytes(out),
+        None => rand::rngs::OsRng.fill_bytes(out),
+    });
+}
+
+// slot dikosongkan lagi walaupun f panic
+struct RngGuard;
+
+impl Drop for RngGuard {
+    fn drop(&mut self) {
+        INJECTED_RNG.with(|slot| *slot.borrow_mut() = None);
+    }
+}
+
+fn with_injected_rng<T>(rng: &mut (impl RngCore + CryptoRng), f: impl FnOnce() -> T) -> T {
+    // dipasang ulang tiap kali, KAT bisa saja sudah mengganti algoritma
+    unsafe { oqs_sys::rand::OQS_randombytes_custom_algorithm(Some(oqs_randombytes)) };
+
+    let ptr: *mut (dyn RngCore + '_) = rng;
+    // aman: pointer hanya dipakai selama f, guard menghapusnya sebelum rng keluar scope
+    let ptr: *mut (dyn RngCore + 'static) = unsafe { std::mem::transmute(ptr) };
+
+    INJECTED_RNG.with(|slot| *slot.borrow_mut() = Some(ptr));
+    let _guard = RngGuard;
+    f()
 }
 
 // ================= PQC =================
@@ -33,12 +128,21 @@ impl PQC {
         Ok(Self { sigalg })
     }
 
-    pub fn keypair(&self) -> Result<(Vec<u8>, Vec<u8>), ChainError> {
-        self.sigalg.keypair().map_err(|_| ChainError::Keypair)
+    pub fn keypair(&self) -> Result<(Vec<u8>, SecretBytes), ChainError> {
+        let (pk, sk) = self.sigalg.keypair().map_err(|_| ChainError::Keypair)?;
+        Ok((pk, SecretBytes::new(sk)))
+    }
+
+    // keypair deterministik untuk test / KAT
+    pub fn keypair_with_rng(
+        &self,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Result<(Vec<u8>, SecretBytes), ChainError> {
+        with_injected_rng(rng, || self.keypair())
     }
 
-    pub fn sign(&self, data: &[u8], sk: &[u8]) -> Result<Vec<u8>, ChainError> {
-        self.sigalg.sign(data, sk).map_err(|_| ChainError::Sign)
+    pub fn sign(&self, data: &[u8], sk: &SecretBytes) -> Result<Vec<u8>, ChainError> {
+        self.sigalg.sign(data, sk.expose()).map_err(|_| ChainError::Sign)
     }
 
     pub fn verify(&self, data: &[u8], sig: &[u8], pk: &[u8]) -> bool {
@@ -48,10 +152,10 @@ impl PQC {
 
 // ================= WALLET =================
 
-#[derive(Clone)]
+#[derive(Debug)]
 pub struct Wallet {
     pub public_key: Vec<u8>,
-    secret_key: Vec<u8>,
+    secret_key: SecretBytes,
 }
 
 impl Wallet {
@@ -63,11 +167,68 @@ impl Wallet {
         })
     }
 
+    pub fn new_with_rng(
+        pqc: &PQC,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Result<Self, ChainError> {
+        let (pk, sk) = pqc.keypair_with_rng(rng)?;
+        Ok(Self {
+            public_key: pk,
+            secret_key: sk,
+        })
+    }
+
     pub fn sign(&self, pqc: &PQC, data: &[u8]) -> Result<Vec<u8>, ChainError> {
         pqc.sign(data, &self.secret_key)
     }
 }
 
+// ================= ENCODING =================
+
+// encoding kanonik untuk semua objek konsensus, dipakai untuk setiap
+// preimage hash dan tanda tangan: [versi u8][tag objek u8][field...].
+// Angka big-endian dengan lebar tetap, bytes/string diawali panjang u32,
+// jadi batas antar field tidak pernah ambigu dan tidak bergantung serde
+pub const ENCODING_VERSION: u8 = 1;
+
+pub const TAG_TRANSACTION: u8 = 0x01;
+pub const TAG_HEADER: u8 = 0x02;
+pub const TAG_SIGNED_TRANSACTION: u8 = 0x03;
+pub const TAG_BLOCK: u8 = 0x04;
+
+pub struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn new(tag: u8) -> Self {
+        Self { buf: vec![ENCODING_VERSION, tag] }
+    }
+
+    pub fn u32(&mut self, x: u32) {
+        self.buf.extend_from_slice(&x.to_be_bytes());
+    }
+
+    pub fn u64(&mut self, x: u64) {
+        self.buf.extend_from_slice(&x.to_be_bytes());
+    }
+
+    // lebar tetap, tanpa prefix panjang
+    pub fn hash32(&mut self, h: &Hash32) {
+        self.buf.extend_from_slice(h);
+    }
+
+    pub fn bytes(&mut self, data: &[u8]) {
+        let len = u32::try_from(data.len()).expect("field lebih dari 4 GiB");
+        self.u32(len);
+        self.buf.extend_from_slice(data);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
 // ================= TRANSACTION =================
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -78,70 +239,594 @@ pub struct Transaction {
 }
 
 impl Transaction {
+    // preimage tanda tangan dan txid; signature sendiri tidak ikut
+    pub fn encode(&self) -> Vec<u8> {
+        let mut enc = Encoder::new(TAG_TRANSACTION);
+        enc.bytes(&self.from);
+        enc.bytes(self.data.as_bytes());
+        enc.finish()
+    }
+
+    // transaksi lengkap termasuk signature: preimage wtxid dan daun Merkle
+    pub fn encode_signed(&self) -> Vec<u8> {
+        let mut enc = Encoder::new(TAG_SIGNED_TRANSACTION);
+        enc.bytes(&self.from);
+        enc.bytes(self.data.as_bytes());
+        enc.bytes(&self.signature);
+        enc.finish()
+    }
+
+    // txid: sama untuk semua tanda tangan atas isi yang sama
     pub fn hash(&self) -> Vec<u8> {
-        let mut hasher = Sha256::new();
-        hasher.update(&self.from);
-        hasher.update(self.data.as_bytes());
-        hasher.finalize().to_vec()
+        Sha256::digest(self.encode()).to_vec()
+    }
+
+    // wtxid: berubah kalau signature berubah
+    pub fn signed_hash(&self) -> Hash32 {
+        Sha256::digest(self.encode_signed()).into()
     }
 
     pub fn verify(&self, pqc: &PQC) -> bool {
-        pqc.verify(&self.hash(), &self.signature, &self.from)
+        pqc.verify(&self.encode(), &self.signature, &self.from)
+    }
+}
+
+// ================= MERKLE =================
+
+pub type Hash32 = [u8; 32];
+
+// gaya RFC 6962: daun = H(0x00 || tx.signed_hash()), node = H(0x01 || kiri || kanan).
+// Prefix yang beda mencegah node dalam menyamar jadi daun; node tanpa
+// pasangan naik apa adanya (tidak diduplikasi seperti Bitcoin, yang bisa
+// membuat dua daftar transaksi berbeda punya root sama). Root yang masuk
+// header = H(0x02 || jumlah daun u64 BE || puncak pohon), jadi bentuk
+// pohon (dan posisi daun di bukti) ikut terikat. Daun memakai wtxid,
+// bukan txid, jadi signature juga terikat ke header
+fn merkle_leaf(tx: &Transaction) -> Hash32 {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(tx.signed_hash());
+    hasher.finalize().into()
+}
+
+fn merkle_node(left: &Hash32, right: &Hash32) -> Hash32 {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn merkle_commit(leaves: usize, top: &Hash32) -> Hash32 {
+    let mut hasher = Sha256::new();
+    hasher.update([0x02]);
+    hasher.update((leaves as u64).to_be_bytes());
+    hasher.update(top);
+    hasher.finalize().into()
+}
+
+fn merkle_parents(level: &[Hash32]) -> Vec<Hash32> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => merkle_node(left, right),
+            [single] => *single,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+// puncak pohon kosong = H("")
+pub fn merkle_root(txs: &[Transaction]) -> Hash32 {
+    let mut level: Vec<Hash32> = txs.iter().map(merkle_leaf).collect();
+    if level.is_empty() {
+        return merkle_commit(0, &Sha256::digest([]).into());
+    }
+
+    while level.len() > 1 {
+        level = merkle_parents(&level);
+    }
+    merkle_commit(txs.len(), &level[0])
+}
+
+// arah setiap langkah tidak disimpan: diturunkan dari index dan jumlah
+// daun, jadi index ikut diautentikasi oleh root
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub index: usize,
+    pub leaves: usize,
+    // hash saudara, dari daun ke puncak
+    pub path: Vec<Hash32>,
+}
+
+// saudara node `pos` di level selebar `len`: Some(true) di kiri,
+// Some(false) di kanan, None kalau node itu naik tanpa pasangan
+fn merkle_sibling(pos: usize, len: usize) -> Option<bool> {
+    if pos % 2 == 1 {
+        Some(true)
+    } else if pos + 1 < len {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+impl MerkleProof {
+    // bukti untuk txs[index]; None kalau index di luar daftar
+    pub fn new(txs: &[Transaction], index: usize) -> Option<Self> {
+        if index >= txs.len() {
+            return None;
+        }
+
+        let mut level: Vec<Hash32> = txs.iter().map(merkle_leaf).collect();
+        let mut pos = index;
+        let mut path = Vec::new();
+
+        while level.len() > 1 {
+            if let Some(left) = merkle_sibling(pos, level.len()) {
+                path.push(level[if left { pos - 1 } else { pos + 1 }]);
+            }
+            level = merkle_parents(&level);
+            pos /= 2;
+        }
+
+        Some(Self {
+            index,
+            leaves: txs.len(),
+            path,
+        })
+    }
+
+    pub fn verify(&self, tx: &Transaction, root: &Hash32) -> bool {
+        if self.index >= self.leaves {
+            return false;
+        }
+
+        let mut acc = merkle_leaf(tx);
+        let mut siblings = self.path.iter();
+        let (mut pos, mut len) = (self.index, self.leaves);
+
+        while len > 1 {
+            if let Some(left) = merkle_sibling(pos, len) {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                acc = if left {
+                    merkle_node(sibling, &acc)
+                } else {
+                    merkle_node(&acc, sibling)
+                };
+            }
+            pos /= 2;
+            len = len.div_ceil(2);
+        }
+
+        // panjang path harus pas dengan bentuk pohon
+        siblings.next().is_none() && merkle_commit(self.leaves, &acc) == *root
     }
 }
 
 // ================= BLOCK =================
 
+pub const BLOCK_VERSION: u32 = 1;
+
+// header di-hash sendiri; body (transaksi) terikat lewat merkle_root,
+// jadi cek PoW, light client, dan sinkronisasi header tidak butuh body
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub version: u32,
+    pub height: u32,
+    pub prev_hash: Hash32, // nol semua untuk genesis
+    pub merkle_root: Hash32,
+    pub timestamp: u64, // detik sejak UNIX epoch
+    pub target: Hash32, // PoW: hash header (big-endian) <= target
+    pub nonce: u64,
+}
+
+impl BlockHeader {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut enc = Encoder::new(TAG_HEADER);
+        enc.u32(self.version);
+        enc.u32(self.height);
+        enc.hash32(&self.prev_hash);
+        enc.hash32(&self.merkle_root);
+        enc.u64(self.timestamp);
+        enc.hash32(&self.target);
+        enc.u64(self.nonce);
+        enc.finish()
+    }
+
+    pub fn hash(&self) -> Hash32 {
+        Sha256::digest(self.encode()).into()
+    }
+
+    // array byte dibandingkan leksikografis = perbandingan angka big-endian
+    pub fn meets_target(&self) -> bool {
+        self.hash() <= self.target
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Block {
-    pub index: u32,
-    pub previous_hash: String,
-    pub hash: String,
+    pub header: BlockHeader,
     pub transactions: Vec<Transaction>,
-    pub nonce: u64,
 }
 
 impl Block {
-    pub fn calculate_hash(
-        index: u32,
-        prev: &str,
-        txs: &Vec<Transaction>,
-        nonce: u64,
-    ) -> Result<String, ChainError> {
-        let data = serde_json::to_string(txs)
-            .map_err(|_| ChainError::Serialize)?;
+    pub fn hash(&self) -> Hash32 {
+        self.header.hash()
+    }
 
-        let input = format!("{}{}{}{}", index, prev, data, nonce);
+    // block lengkap: header, jumlah transaksi u32, lalu tiap transaksi
+    // bertanda tangan; semuanya diawali panjang
+    pub fn encode(&self) -> Vec<u8> {
+        let mut enc = Encoder::new(TAG_BLOCK);
+        enc.bytes(&self.header.encode());
+        enc.u32(u32::try_from(self.transactions.len()).expect("transaksi lebih dari 2^32"));
+        for tx in &self.transactions {
+            enc.bytes(&tx.encode_signed());
+        }
+        enc.finish()
+    }
 
-        let mut hasher = Sha256::new();
-        hasher.update(input);
+    pub fn merkle_proof(&self, index: usize) -> Option<MerkleProof> {
+        MerkleProof::new(&self.transactions, index)
+    }
 
-        Ok(format!("{:x}", hasher.finalize()))
+    pub fn verify_proof(&self, tx: &Transaction, proof: &MerkleProof) -> bool {
+        proof.verify(tx, &self.header.merkle_root)
     }
 
     pub fn mine(
-        index: u32,
-        prev: String,
+        height: u32,
+        prev: Hash32,
         txs: Vec<Transaction>,
-        difficulty: usize,
-    ) -> Result<Self, ChainError> {
-        let mut nonce = 0;
-
-        loop {
-            let hash = Self::calculate_hash(index, &prev, &txs, nonce)?;
-
-            if hash.starts_with(&"0".repeat(difficulty)) {
-                return Ok(Self {
-                    index,
-                    previous_hash: prev,
-                    hash,
-                    transactions: txs,
-                    nonce,
-                });
+        target: Hash32,
+        timestamp: u64,
+    ) -> Self {
+        let mut header = BlockHeader {
+            version: BLOCK_VERSION,
+            height,
+            prev_hash: prev,
+            merkle_root: merkle_root(&txs),
+            timestamp,
+            target,
+            nonce: 0,
+        };
+
+        while !header.meets_target() {
+            header.nonce += 1;
+        }
+
+        Self {
+            header,
+            transactions: txs,
+        }
+    }
+}
+
+// ================= DIFFICULTY =================
+
+// timestamp header boleh paling jauh sekian detik di depan jam lokal
+pub const MAX_FUTURE_DRIFT: u64 = 2 * 60 * 60;
+
+// target yang setara dengan "hash hex diawali `zeros` angka nol"
+pub fn target_from_zeros(zeros: usize) -> Hash32 {
+    let mut target = [0xff; 32];
+    for nibble in 0..zeros.min(64) {
+        target[nibble / 2] &= if nibble % 2 == 0 { 0x0f } else { 0xf0 };
+    }
+    target
+}
+
+// perkiraan jumlah hash untuk satu block: 2^256 / (target + 1)
+pub fn target_work(target: &Hash32) -> f64 {
+    let t = target.iter().fold(0.0, |acc, &b| acc * 256.0 + b as f64);
+    2f64.powi(256) / (t + 1.0)
+}
+
+// target * num / den sebagai angka 256-bit big-endian; None kalau hasilnya
+// tidak muat di 256 bit
+fn scale_target(target: &Hash32, num: u64, den: u64) -> Option<Hash32> {
+    // 256 bit × 64 bit muat di 40 byte
+    let mut wide = [0u8; 40];
+    let mut carry = 0u128;
+    for i in (0..40).rev() {
+        let byte = if i >= 8 { target[i - 8] } else { 0 };
+        carry += byte as u128 * num as u128;
+        wide[i] = carry as u8;
+        carry >>= 8;
+    }
+
+    let mut rem = 0u128;
+    for b in wide.iter_mut() {
+        let cur = (rem << 8) | *b as u128;
+        *b = (cur / den as u128) as u8;
+        rem = cur % den as u128;
+    }
+
+    if wide[..8].iter().any(|&b| b != 0) {
+        return None;
+    }
+    wide[8..].try_into().ok()
+}
+
+// gaya Bitcoin: target tetap selama `interval` block, lalu diskalakan dengan
+// (waktu periode sebenarnya / waktu yang diharapkan), dibatasi 4x ke atas
+// maupun ke bawah dan tidak pernah lebih mudah dari `limit`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Retarget {
+    pub initial: Hash32, // target genesis dan periode pertama
+    pub limit: Hash32,   // target paling mudah yang diterima
+    pub interval: u32,   // panjang periode, dalam block
+    pub spacing: u64,    // jarak antar block yang dituju, dalam detik
+}
+
+impl Retarget {
+    pub fn new(initial: Hash32, interval: u32, spacing: u64) -> Self {
+        assert!(interval >= 2, "interval retarget minimal 2 block");
+        assert!(spacing >= 1, "spacing minimal 1 detik");
+
+        Self {
+            initial,
+            limit: initial,
+            interval,
+            spacing,
+        }
+    }
+
+    // target wajib untuk block sesudah `headers` (rantai dari genesis)
+    pub fn next_target(&self, headers: &[BlockHeader]) -> Hash32 {
+        let Some(last) = headers.last() else {
+            return self.initial;
+        };
+
+        let height = headers.len();
+        let interval = self.interval as usize;
+        if !height.is_multiple_of(interval) {
+            return last.target;
+        }
+
+        // periode = block pertama sampai terakhir, jadi interval - 1 jarak
+        let first = &headers[height - interval];
+        let expected = self.spacing * (self.interval as u64 - 1);
+        let actual = last
+            .timestamp
+            .saturating_sub(first.timestamp)
+            .clamp(expected.div_ceil(4), expected * 4);
+
+        scale_target(&last.target, actual, expected)
+            .unwrap_or(self.limit)
+            .min(self.limit)
+    }
+}
+
+// cek rantai header saja, mulai dari genesis: versi, nomor, link,
+// timestamp (tidak mundur, tidak terlalu jauh di depan `now`), target
+// hasil retarget, dan PoW
+pub fn check_headers(headers: &[BlockHeader], params: &Retarget, now: u64) -> Result<(), ChainError> {
+    for (i, header) in headers.iter().enumerate() {
+        if header.version != BLOCK_VERSION || header.height as usize != i {
+            return Err(ChainError::InvalidBlock(i));
+        }
+
+        if header.timestamp > now.saturating_add(MAX_FUTURE_DRIFT) {
+            return Err(ChainError::InvalidBlock(i));
+        }
+
+        // cek PoW
+        if header.target != params.next_target(&headers[..i]) || !header.meets_target() {
+            return Err(ChainError::InvalidBlock(i));
+        }
+
+        // cek link dan timestamp
+        match i.checked_sub(1).map(|p| &headers[p]) {
+            Some(prev) => {
+                if header.prev_hash != prev.hash() || header.timestamp < prev.timestamp {
+                    return Err(ChainError::InvalidBlock(i));
+                }
             }
+            None => {
+                if header.prev_hash != [0; 32] {
+                    return Err(ChainError::InvalidBlock(i));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ================= STORAGE =================
+
+// file append-only: STORE_MAGIC lalu record berurutan
+//   [panjang payload u32 LE][crc32 payload u32 LE][crc32 8 byte tadi u32 LE][payload = block JSON]
+// tiap append langsung di-fsync, jadi crash hanya bisa meninggalkan
+// awalan record terakhir; awalan itu dibuang saat open. Byte lain yang
+// tidak cocok dengan checksum-nya (termasuk panjang yang rusak) berarti
+// file korup, tidak dipotong
+const STORE_MAGIC: &[u8; 8] = b"PQCBLK02";
+const RECORD_HEADER: usize = 12;
 
-            nonce += 1;
+fn store_io<T>(r: std::io::Result<T>) -> Result<T, ChainError> {
+    r.map_err(|_| ChainError::StoreIo)
+}
+
+// CRC-32 IEEE (poly 0xEDB88320), versi bit per bit
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+fn encode_record(block: &Block) -> Result<Vec<u8>, ChainError> {
+    let payload = serde_json::to_vec(block).map_err(|_| ChainError::Serialize)?;
+    let len = u32::try_from(payload.len()).map_err(|_| ChainError::Serialize)?;
+
+    let mut record = Vec::with_capacity(RECORD_HEADER + payload.len());
+    record.extend_from_slice(&len.to_le_bytes());
+    record.extend_from_slice(&crc32(&payload).to_le_bytes());
+    record.extend_from_slice(&crc32(&record).to_le_bytes());
+    record.extend_from_slice(&payload);
+    Ok(record)
+}
+
+// header lengkap -> (panjang payload, crc32 payload), setelah crc
+// header sendiri dicek; panjang tidak pernah dipakai sebelum itu
+fn decode_header(header: &[u8]) -> Result<(usize, u32), ChainError> {
+    let word = |i: usize| u32::from_le_bytes([header[i], header[i + 1], header[i + 2], header[i + 3]]);
+    if crc32(&header[..8]) != word(8) {
+        return Err(ChainError::StoreFormat);
+    }
+    Ok((word(0) as usize, word(4)))
+}
+
+// record utuh di data[pos..] -> (block, posisi record berikutnya);
+// None kalau data[pos..] awalan sejati dari sebuah record (ekor bekas
+// crash): header terpotong, atau header valid tapi payload terpotong.
+// Header atau payload lengkap yang checksum-nya salah, atau payload
+// yang bukan block, adalah error, bukan sesuatu yang boleh dipotong
+fn decode_record(data: &[u8], pos: usize) -> Result<Option<(Block, usize)>, ChainError> {
+    let rest = data.get(pos..).unwrap_or_default();
+    if rest.len() < RECORD_HEADER {
+        // 8 byte pertama sudah menentukan crc header, byte crc yang
+        // sempat tertulis harus cocok
+        if rest.len() > 8 && crc32(&rest[..8]).to_le_bytes()[..rest.len() - 8] != rest[8..] {
+            return Err(ChainError::StoreFormat);
         }
+        return Ok(None);
+    }
+    let (len, crc) = decode_header(&rest[..RECORD_HEADER])?;
+
+    let start = pos + RECORD_HEADER;
+    let Some(payload) = data.get(start..start.saturating_add(len)) else {
+        return Ok(None);
+    };
+    if crc32(payload) != crc {
+        return Err(ChainError::StoreFormat);
+    }
+
+    let block = serde_json::from_slice(payload).map_err(|_| ChainError::StoreFormat)?;
+    Ok(Some((block, start + len)))
+}
+
+pub struct BlockStore {
+    file: File,
+    offsets: Vec<u64>,               // height -> offset record
+    heights: HashMap<Hash32, usize>, // hash -> height
+    end: u64,                        // akhir record utuh terakhir
+    pub truncated: u64,              // byte ekor rusak yang dibuang saat open
+}
+
+impl BlockStore {
+    // buka (atau buat) file, baca semua record utuh, potong ekor yang rusak
+    pub fn open(path: impl AsRef<Path>) -> Result<(Self, Vec<Block>), ChainError> {
+        let mut file = store_io(
+            OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path),
+        )?;
+
+        let mut data = Vec::new();
+        store_io(file.read_to_end(&mut data))?;
+
+        // file kosong, atau crash saat magic baru setengah ditulis
+        if data.len() < STORE_MAGIC.len() && STORE_MAGIC.starts_with(&data) {
+            store_io(file.set_len(0))?;
+            store_io(file.seek(SeekFrom::Start(0)))?;
+            store_io(file.write_all(STORE_MAGIC))?;
+            store_io(file.sync_all())?;
+            data = STORE_MAGIC.to_vec();
+        } else if !data.starts_with(STORE_MAGIC) {
+            return Err(ChainError::StoreFormat);
+        }
+
+        let mut store = Self {
+            file,
+            offsets: Vec::new(),
+            heights: HashMap::new(),
+            end: 0,
+            truncated: 0,
+        };
+
+        let mut blocks = Vec::new();
+        let mut pos = STORE_MAGIC.len();
+        while let Some((block, next)) = decode_record(&data, pos)? {
+            store.index(&block, pos as u64);
+            blocks.push(block);
+            pos = next;
+        }
+
+        if pos < data.len() {
+            store_io(store.file.set_len(pos as u64))?;
+            store_io(store.file.sync_all())?;
+            store.truncated = (data.len() - pos) as u64;
+        }
+        store.end = pos as u64;
+
+        Ok((store, blocks))
+    }
+
+    fn index(&mut self, block: &Block, offset: u64) {
+        self.heights.insert(block.hash(), self.offsets.len());
+        self.offsets.push(offset);
+    }
+
+    // tulis satu record lalu fsync; kalau gagal di tengah jalan,
+    // `end` tidak maju dan append berikutnya memotong sisa tulisan itu
+    // dulu, supaya record yang lebih pendek tidak diikuti sampah
+    pub fn append(&mut self, block: &Block) -> Result<(), ChainError> {
+        let record = encode_record(block)?;
+
+        store_io(self.file.set_len(self.end))?;
+        store_io(self.file.seek(SeekFrom::Start(self.end)))?;
+        store_io(self.file.write_all(&record))?;
+        store_io(self.file.sync_data())?;
+
+        self.index(block, self.end);
+        self.end += record.len() as u64;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    pub fn height_of(&self, hash: &Hash32) -> Option<usize> {
+        self.heights.get(hash).copied()
+    }
+
+    // baca ulang dari disk lewat index, checksum dicek lagi
+    pub fn read(&self, height: usize) -> Result<Block, ChainError> {
+        let offset = *self.offsets.get(height).ok_or(ChainError::InvalidBlock(height))?;
+
+        let mut file = &self.file;
+        let mut header = [0u8; RECORD_HEADER];
+        store_io(file.seek(SeekFrom::Start(offset)))?;
+        store_io(file.read_exact(&mut header))?;
+
+        let (len, _) = decode_header(&header)?;
+        let mut data = header.to_vec();
+        data.resize(RECORD_HEADER + len, 0);
+        store_io(file.read_exact(&mut data[RECORD_HEADER..]))?;
+
+        decode_record(&data, 0)?
+            .map(|(block, _)| block)
+            .ok_or(ChainError::StoreFormat)
     }
 }
 
@@ -149,97 +834,661 @@ impl Block {
 
 pub struct Blockchain {
     pub chain: Vec<Block>,
-    difficulty: usize,
+    params: Retarget,
+    store: Option<BlockStore>,
 }
 
 impl Blockchain {
-    pub fn new(difficulty: usize) -> Result<Self, ChainError> {
-        let genesis = Block::mine(0, "0".into(), vec![], difficulty)?;
+    // hanya di memori
+    pub fn new(params: Retarget) -> Result<Self, ChainError> {
+        let genesis = Block::mine(0, [0; 32], vec![], params.initial, now_secs());
         Ok(Self {
             chain: vec![genesis],
-            difficulty,
+            params,
+            store: None,
         })
     }
 
+    // muat chain dari file block (genesis ditambang kalau file masih kosong),
+    // lalu cek ulang seluruhnya, tanda tangan termasuk (lihat check)
+    pub fn open(path: impl AsRef<Path>, params: Retarget, pqc: &PQC) -> Result<Self, ChainError> {
+        let (store, blocks) = BlockStore::open(path)?;
+
+        let mut chain = Self {
+            chain: blocks,
+            params,
+            store: Some(store),
+        };
+
+        if chain.chain.is_empty() {
+            let genesis = Block::mine(0, [0; 32], vec![], params.initial, now_secs());
+            chain.commit(genesis)?;
+        }
+
+        chain.check(pqc)?;
+        Ok(chain)
+    }
+
+    pub fn store(&self) -> Option<&BlockStore> {
+        self.store.as_ref()
+    }
+
+    pub fn params(&self) -> &Retarget {
+        &self.params
+    }
+
+    pub fn headers(&self) -> Vec<BlockHeader> {
+        self.chain.iter().map(|block| block.header.clone()).collect()
+    }
+
+    // simpan dulu ke disk, baru masuk ke chain di memori
+    fn commit(&mut self, block: Block) -> Result<(), ChainError> {
+        if let Some(store) = self.store.as_mut() {
+            store.append(&block)?;
+        }
+        self.chain.push(block);
+        Ok(())
+    }
+
     pub fn add_block(&mut self, txs: Vec<Transaction>) -> Result<(), ChainError> {
-        let last_hash = self.chain.last()
-            .ok_or(ChainError::Serialize)?
-            .hash
-            .clone();
+        self.add_block_at(txs, now_secs())
+    }
+
+    // `timestamp` dari jam luar (jam tiruan di test retarget)
+    pub fn add_block_at(&mut self, txs: Vec<Transaction>, timestamp: u64) -> Result<(), ChainError> {
+        let target = self.params.next_target(&self.headers());
+        let last = self.chain.last().ok_or(ChainError::Serialize)?;
+
+        // jam mundur tidak boleh menghasilkan block yang ditolak sendiri
+        let timestamp = timestamp.max(last.header.timestamp);
 
         let block = Block::mine(
             self.chain.len() as u32,
-            last_hash,
+            last.hash(),
             txs,
-            self.difficulty,
-        )?;
+            target,
+            timestamp,
+        );
 
-        self.chain.push(block);
-        Ok(())
+        self.commit(block)
     }
 
-    pub fn is_valid(&self, pqc: &PQC) -> bool {
-        for i in 0..self.chain.len() {
-            let block = &self.chain[i];
-
-            // cek hash
-            let recalculated = match Block::calculate_hash(
-                block.index,
-                &block.previous_hash,
-                &block.transactions,
-                block.nonce,
-            ) {
-                Ok(h) => h,
-                Err(_) => return false,
-            };
-
-            if block.hash != recalculated {
-                return false;
+    // header (lihat check_headers), lalu body: merkle root harus cocok
+    // dengan transaksinya; tanpa cek tanda tangan
+    pub fn check_links(&self) -> Result<(), ChainError> {
+        check_headers(&self.headers(), &self.params, now_secs())?;
+
+        for (i, block) in self.chain.iter().enumerate() {
+            // cek merkle root
+            if block.header.merkle_root != merkle_root(&block.transactions) {
+                return Err(ChainError::InvalidBlock(i));
             }
+        }
+
+        Ok(())
+    }
 
-            // cek link
-            if i > 0 && block.previous_hash != self.chain[i - 1].hash {
-                return false;
+    // check_links, lalu tanda tangan setiap transaksi
+    pub fn check(&self, pqc: &PQC) -> Result<(), ChainError> {
+        self.check_links()?;
+
+        for (i, block) in self.chain.iter().enumerate() {
+            if !block.transactions.iter().all(|tx| tx.verify(pqc)) {
+                return Err(ChainError::InvalidBlock(i));
             }
+        }
 
-            // cek transaksi
-            for tx in &block.transactions {
-                if !tx.verify(pqc) {
-                    return false;
-                }
+        Ok(())
+    }
+
+    pub fn is_valid(&self, pqc: &PQC) -> bool {
+        self.check(pqc).is_ok()
+    }
+}
+
+// ================= KAT =================
+
+const KAT_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/kat/dilithium2.rsp");
+const KAT_COUNT: usize = 10;
+
+// DRBG AES-256 CTR NIST bawaan liboqs; selama aktif, keypair/sign memakai ini
+fn kat_switch_rng(algorithm: &[u8]) -> Result<(), ChainError> {
+    let status = unsafe { oqs_sys::rand::OQS_randombytes_switch_algorithm(algorithm.as_ptr().cast()) };
+    match status {
+        oqs_sys::common::OQS_STATUS::OQS_SUCCESS => Ok(()),
+        _ => Err(ChainError::KatDrbg),
+    }
+}
+
+fn kat_seed_rng(seed: &[u8]) -> Result<(), ChainError> {
+    let seed: &[u8; 48] = seed.try_into().map_err(|_| ChainError::KatDrbg)?;
+    kat_switch_rng(b"NIST-KAT\0")?;
+    unsafe { oqs_sys::rand::OQS_randombytes_nist_kat_init_256bit(seed.as_ptr(), std::ptr::null()) };
+    Ok(())
+}
+
+fn kat_random_bytes(len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    unsafe { oqs_sys::rand::OQS_randombytes(buf.as_mut_ptr(), len) };
+    buf
+}
+
+// field pk, sk, smlen, sm untuk satu (seed, msg), sama seperti PQCgenKAT_sign
+fn kat_fields(pqc: &PQC, seed: &[u8], msg: &[u8]) -> Result<Vec<(&'static str, String)>, ChainError> {
+    kat_seed_rng(seed)?;
+    let (pk, sk) = pqc.keypair()?;
+    let sig = pqc.sign(msg, &sk)?;
+
+    if !pqc.verify(msg, &sig, &pk) {
+        return Err(ChainError::Sign);
+    }
+
+    let sm = [sig, msg.to_vec()].concat();
+    Ok(vec![
+        ("pk", hex::encode_upper(&pk)),
+        ("sk", hex::encode_upper(sk.expose())),
+        ("smlen", sm.len().to_string()),
+        ("sm", hex::encode_upper(&sm)),
+    ])
+}
+
+pub fn generate_kat(pqc: &PQC, count: usize) -> Result<String, ChainError> {
+    // seed dan pesan semuanya dari DRBG dengan entropy 0, 1, ..., 47
+    let entropy: Vec<u8> = (0..48).collect();
+    kat_seed_rng(&entropy)?;
+
+    let inputs: Vec<(Vec<u8>, Vec<u8>)> = (0..count)
+        .map(|i| (kat_random_bytes(48), kat_random_bytes(33 * (i + 1))))
+        .collect();
+
+    let mut out = String::from("# Dilithium2\n\n");
+    let result = inputs.iter().enumerate().try_for_each(|(i, (seed, msg))| {
+        out += &format!("count = {}\n", i);
+        out += &format!("seed = {}\n", hex::encode_upper(seed));
+        out += &format!("mlen = {}\n", msg.len());
+        out += &format!("msg = {}\n", hex::encode_upper(msg));
+        for (field, value) in kat_fields(pqc, seed, msg)? {
+            out += &format!("{} = {}\n", field, value);
+        }
+        out += "\n";
+        Ok(())
+    });
+
+    kat_switch_rng(b"system\0")?;
+    result.map(|_| out)
+}
+
+pub fn check_kat(pqc: &PQC, rsp: &str) -> Result<usize, ChainError> {
+    let result = check_kat_records(pqc, rsp);
+    kat_switch_rng(b"system\0")?;
+    result
+}
+
+fn check_kat_records(pqc: &PQC, rsp: &str) -> Result<usize, ChainError> {
+    let mut checked = 0;
+    let mut record: Vec<(String, String)> = Vec::new();
+
+    // baris kosong di akhir menutup record terakhir
+    for (i, line) in rsp.lines().chain([""]).enumerate() {
+        let line = line.trim();
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if !line.is_empty() {
+            let (key, value) = line.split_once(" = ").ok_or(ChainError::KatParse(i + 1))?;
+            record.push((key.into(), value.into()));
+            continue;
+        }
+
+        if record.is_empty() {
+            continue;
+        }
+
+        let get = |name: &str| {
+            record
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.as_str())
+                .ok_or(ChainError::KatParse(i))
+        };
+
+        let count: usize = get("count")?.parse().map_err(|_| ChainError::KatParse(i))?;
+        let seed = hex::decode(get("seed")?).map_err(|_| ChainError::KatParse(i))?;
+        let msg = hex::decode(get("msg")?).map_err(|_| ChainError::KatParse(i))?;
+
+        for (field, value) in kat_fields(pqc, &seed, &msg)? {
+            if get(field)? != value {
+                return Err(ChainError::KatMismatch { count, field: field.into() });
             }
         }
 
-        true
+        checked += 1;
+        record.clear();
     }
+
+    Ok(checked)
 }
 
 // ================= MAIN =================
 
+const CHAIN_FILE: &str = "pqc_chain.blk";
+
+// retarget setiap 2016 block menuju 10 menit per block, seperti Bitcoin
+const CHAIN_INTERVAL: u32 = 2016;
+const CHAIN_SPACING: u64 = 600;
+
 fn main() -> Result<(), ChainError> {
     let pqc = PQC::new()?;
 
-    let wallet = Wallet::new(&pqc)?;
+    // `kat` mencocokkan tests/kat/dilithium2.rsp, `kat-gen` menulis ulang
+    match std::env::args().nth(1).as_deref() {
+        Some("kat") => {
+            let rsp = std::fs::read_to_string(KAT_FILE).map_err(|_| ChainError::KatIo)?;
+            println!("KAT Dilithium2: {} vektor cocok", check_kat(&pqc, &rsp)?);
+            return Ok(());
+        }
+        Some("kat-gen") => {
+            let rsp = generate_kat(&pqc, KAT_COUNT)?;
+            std::fs::write(KAT_FILE, rsp).map_err(|_| ChainError::KatIo)?;
+            println!("KAT Dilithium2: {} vektor ditulis", KAT_COUNT);
+            return Ok(());
+        }
+        _ => {}
+    }
 
-    // buat transaksi
-    let message = b"Transfer PQC Data";
-    let signature = wallet.sign(&pqc, message)?;
+    let wallet = Wallet::new(&pqc)?;
 
-    let tx = Transaction {
+    // buat transaksi, ditandatangani atas encoding kanoniknya
+    let mut tx = Transaction {
         from: wallet.public_key.clone(),
         data: "Transfer PQC Data".into(),
-        signature,
+        signature: vec![],
     };
+    tx.signature = wallet.sign(&pqc, &tx.encode())?;
+
+    // blockchain, disimpan di CHAIN_FILE dan dilanjutkan setiap run
+    let mut chain = Blockchain::open(
+        CHAIN_FILE,
+        Retarget::new(target_from_zeros(3), CHAIN_INTERVAL, CHAIN_SPACING),
+        &pqc,
+    )?;
 
-    // blockchain
-    let mut chain = Blockchain::new(3)?;
+    println!("=== PQC BLOCKCHAIN FINAL ===");
+    if let Some(store) = chain.store() {
+        println!("Dimuat dari disk: {} block", store.len());
+        if store.truncated > 0 {
+            println!("Ekor rusak dibuang: {} byte", store.truncated);
+        }
+    }
 
     chain.add_block(vec![tx.clone()])?;
     chain.add_block(vec![tx])?;
 
-    println!("=== PQC BLOCKCHAIN FINAL ===");
     println!("Jumlah block: {}", chain.chain.len());
+    println!("Ukuran block terakhir (kanonik): {} byte", chain.chain[chain.chain.len() - 1].encode().len());
     println!("Valid: {}", chain.is_valid(&pqc));
 
+    let tip = &chain.chain[chain.chain.len() - 1];
+    println!(
+        "Header terakhir: versi {}, height {}, timestamp {}",
+        tip.header.version, tip.header.height, tip.header.timestamp
+    );
+    println!(
+        "Header valid (tanpa body): {}",
+        check_headers(&chain.headers(), chain.params(), now_secs()).is_ok()
+    );
+    if let Some(store) = chain.store() {
+        if let Some(height) = store.height_of(&tip.hash()) {
+            let stored = store.read(height)?;
+            println!("Block terakhir di height {}, sama dengan di disk: {}", height, stored.hash() == tip.hash());
+        }
+    }
+
+    // bukti Merkle untuk satu transaksi, tanpa mengirim seluruh block
+    if let Some(proof) = tip.merkle_proof(0) {
+        println!("Bukti Merkle tx #0: {}", tip.verify_proof(&tip.transactions[0], &proof));
+
+        let mut forged = tip.transactions[0].clone();
+        forged.data.push('!');
+        println!("Bukti Merkle tx palsu: {}", tip.verify_proof(&forged, &proof));
+
+        // txid tetap, tapi daun memakai wtxid: signature lain tidak lolos
+        let mut resigned = tip.transactions[0].clone();
+        resigned.signature.push(0);
+        println!(
+            "Signature diganti: txid sama {}, bukti Merkle {}",
+            resigned.hash() == tip.transactions[0].hash(),
+            tip.verify_proof(&resigned, &proof)
+        );
+    }
+
     Ok(())
           }
+
+// ================= TEST =================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // tests/kat/dilithium2.rsp mengunci wrapper Dilithium: perubahan yang
+    // menggeser pk, sk, atau tanda tangan membuat test ini gagal
+    #[test]
+    fn kat_dilithium2() {
+        let pqc = PQC::new().expect("PQC init");
+        let rsp = std::fs::read_to_string(KAT_FILE).expect("file KAT");
+
+        match check_kat(&pqc, &rsp) {
+            Ok(n) => assert_eq!(n, KAT_COUNT),
+            Err(e) => panic!("KAT Dilithium2: {}", e),
+        }
+    }
+
+    // jam tiruan: maju sebanyak waktu yang dibutuhkan hashrate simulasi untuk
+    // menambang satu block pada target saat itu, jadi retarget bisa diamati
+    // tanpa menunggu waktu sungguhan
+    struct MockClock {
+        now: u64,
+    }
+
+    impl MockClock {
+        fn mine(&mut self, target: &Hash32, hashrate: f64) -> u64 {
+            self.now += (target_work(target) / hashrate).round() as u64;
+            self.now
+        }
+    }
+
+    // hashrate awal pas untuk `spacing`, lalu naik 4x, lalu turun lagi; di
+    // akhir tiap fase rata-rata jarak block harus kembali dekat `spacing`
+    #[test]
+    fn retarget_follows_hashrate() {
+        let params = Retarget::new(target_from_zeros(2), 8, 10);
+        let interval = params.interval as usize;
+
+        let base = target_work(&params.initial) / params.spacing as f64;
+        let phases = [(base, 3), (4.0 * base, 4), (base, 4)];
+
+        let mut chain = Blockchain::new(params).expect("genesis");
+        let mut clock = MockClock {
+            now: chain.chain[0].header.timestamp,
+        };
+
+        for (phase, (hashrate, periods)) in phases.into_iter().enumerate() {
+            // tambang sampai akhir periode retarget
+            for _ in 0..periods * interval - usize::from(phase == 0) {
+                let target = params.next_target(&chain.headers());
+                let timestamp = clock.mine(&target, hashrate);
+                chain.add_block_at(vec![], timestamp).expect("tambang");
+            }
+            assert!(chain.chain.len().is_multiple_of(interval));
+
+            let period = &chain.chain[chain.chain.len() - interval..];
+            let span = period[interval - 1].header.timestamp - period[0].header.timestamp;
+            let average = span as f64 / (interval - 1) as f64;
+            let spacing = params.spacing as f64;
+            assert!(
+                (average - spacing).abs() <= 0.1 * spacing,
+                "fase {}: rata-rata {:.1} s per block",
+                phase,
+                average
+            );
+        }
+
+        let mut headers = chain.headers();
+        assert!(check_headers(&headers, &params, clock.now).is_ok());
+
+        // target yang tidak mengikuti retarget ditolak, walau PoW-nya lolos
+        let last = headers.len() - 1;
+        assert_ne!(headers[last].target, params.limit);
+        headers[last].target = params.limit;
+        while !headers[last].meets_target() {
+            headers[last].nonce += 1;
+        }
+        assert!(matches!(
+            check_headers(&headers, &params, clock.now),
+            Err(ChainError::InvalidBlock(i)) if i == last
+        ));
+    }
+
+    // file block sementara berisi `count` block dengan target paling mudah;
+    // mengembalikan path dan offset tiap record
+    fn temp_store(name: &str, count: usize) -> (std::path::PathBuf, Vec<u64>) {
+        let path = std::env::temp_dir().join(format!("pqc_chain_{}_{}.blk", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let (mut store, _) = BlockStore::open(&path).expect("buka store");
+        let mut prev = [0; 32];
+        for height in 0..count {
+            let block = Block::mine(height as u32, prev, vec![], [0xff; 32], 0);
+            prev = block.hash();
+            store.append(&block).expect("append");
+        }
+
+        (path, store.offsets.clone())
+    }
+
+    fn flip_byte(path: &Path, offset: u64) {
+        let mut data = std::fs::read(path).expect("baca store");
+        data[offset as usize] ^= 0x01;
+        std::fs::write(path, data).expect("tulis store");
+    }
+
+    fn set_len(path: &Path, len: u64) {
+        OpenOptions::new().write(true).open(path).expect("buka").set_len(len).expect("set_len");
+    }
+
+    // crash di tengah append terakhir: awalan record itu dibuang, sisanya
+    // utuh, dan append berikutnya menulis di tempatnya
+    #[test]
+    fn store_truncates_torn_tail() {
+        // payload terpotong (header utuh), header terpotong di tengah
+        // crc-nya, hanya sebagian field panjang
+        for keep in [None, Some(10), Some(3)] {
+            let (path, offsets) = temp_store("torn", 5);
+            let len = std::fs::metadata(&path).expect("metadata").len();
+            let cut = keep.map_or(len - 10, |k| offsets[4] + k);
+
+            set_len(&path, cut);
+            let (mut store, blocks) = BlockStore::open(&path).expect("ekor terpotong dibuang");
+            assert_eq!(blocks.len(), 4, "potong di {}", cut);
+            assert_eq!(store.truncated, cut - offsets[4]);
+
+            store.append(&blocks[3]).expect("append sesudah potong");
+            drop(store);
+            let (_, blocks) = BlockStore::open(&path).expect("buka ulang");
+            assert_eq!(blocks.len(), 5);
+
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+
+    // record rusak bukan bekas crash, juga di record terakhir: open harus
+    // gagal dan file tidak boleh disentuh
+    #[test]
+    fn store_rejects_corruption_before_tail() {
+        let (path, offsets) = temp_store("corrupt", 5);
+        let len = std::fs::metadata(&path).expect("metadata").len();
+
+        // payload record di tengah, lalu payload record terakhir yang lengkap
+        for offset in [offsets[1] + RECORD_HEADER as u64 + 5, len - 1] {
+            flip_byte(&path, offset);
+            assert!(matches!(BlockStore::open(&path), Err(ChainError::StoreFormat)));
+            assert_eq!(std::fs::metadata(&path).expect("metadata").len(), len);
+            flip_byte(&path, offset);
+        }
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // panjang yang rusak menunjuk melewati akhir file; tanpa crc header
+    // record itu (dan semua sesudahnya) terlihat seperti ekor terpotong
+    #[test]
+    fn store_rejects_corrupt_length() {
+        let (path, offsets) = temp_store("length", 5);
+        let len = std::fs::metadata(&path).expect("metadata").len();
+
+        for offset in [offsets[1] + 3, offsets[4] + 3, offsets[4] + 9] {
+            flip_byte(&path, offset);
+            assert!(matches!(BlockStore::open(&path), Err(ChainError::StoreFormat)), "byte {}", offset);
+            assert_eq!(std::fs::metadata(&path).expect("metadata").len(), len);
+            flip_byte(&path, offset);
+        }
+
+        // header terpotong yang byte crc-nya sudah salah juga bukan awalan
+        set_len(&path, offsets[4] + 10);
+        flip_byte(&path, offsets[4] + 9);
+        assert!(matches!(BlockStore::open(&path), Err(ChainError::StoreFormat)));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // open mengecek tanda tangan, bukan hanya header dan merkle root
+    #[test]
+    fn open_rejects_bad_signature() {
+        let pqc = PQC::new().expect("pqc");
+        let wallet = Wallet::new(&pqc).expect("wallet");
+        let params = Retarget::new([0xff; 32], 100, 1);
+        let path = std::env::temp_dir().join(format!("pqc_chain_sig_{}.blk", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut tx = Transaction {
+            from: wallet.public_key.clone(),
+            data: "ditandatangani".into(),
+            signature: vec![],
+        };
+        tx.signature = wallet.sign(&pqc, &tx.encode()).expect("sign");
+
+        let mut chain = Blockchain::open(&path, params, &pqc).expect("buka");
+        chain.add_block(vec![tx.clone()]).expect("block sah");
+        drop(chain);
+        assert_eq!(Blockchain::open(&path, params, &pqc).expect("buka ulang").chain.len(), 2);
+
+        let mut forged = tx;
+        forged.data.push('!');
+        let mut chain = Blockchain::open(&path, params, &pqc).expect("buka ulang");
+        chain.add_block(vec![forged]).expect("block tanpa cek tanda tangan");
+        drop(chain);
+        assert!(matches!(Blockchain::open(&path, params, &pqc), Err(ChainError::InvalidBlock(2))));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    fn dummy_txs(n: usize) -> Vec<Transaction> {
+        (0..n)
+            .map(|i| Transaction {
+                from: vec![i as u8],
+                data: format!("tx {}", i),
+                signature: vec![],
+            })
+            .collect()
+    }
+
+    // setiap index di setiap ukuran pohon terbukti; index, jumlah daun,
+    // atau panjang path yang diubah ditolak
+    #[test]
+    fn merkle_proof_binds_position() {
+        for n in 1..=17 {
+            let txs = dummy_txs(n);
+            let root = merkle_root(&txs);
+
+            for (i, tx) in txs.iter().enumerate() {
+                let proof = MerkleProof::new(&txs, i).expect("index dalam daftar");
+                assert!(proof.verify(tx, &root), "n={} i={}", n, i);
+
+                for j in (0..n + 1).filter(|&j| j != i) {
+                    let moved = MerkleProof { index: j, ..proof.clone() };
+                    assert!(!moved.verify(tx, &root), "n={} i={} -> {}", n, i, j);
+                }
+
+                let resized = MerkleProof { leaves: n + 1, ..proof.clone() };
+                assert!(!resized.verify(tx, &root), "n={} i={}: jumlah daun", n, i);
+
+                let mut longer = proof.clone();
+                longer.path.push([0; 32]);
+                assert!(!longer.verify(tx, &root), "n={} i={}: path kepanjangan", n, i);
+
+                if !proof.path.is_empty() {
+                    let mut shorter = proof.clone();
+                    shorter.path.pop();
+                    assert!(!shorter.verify(tx, &root), "n={} i={}: path kependekan", n, i);
+                }
+            }
+        }
+
+        assert!(MerkleProof::new(&dummy_txs(3), 3).is_none());
+    }
+
+    // preimage lama menempelkan field tanpa pemisah: index 1 + prev "23…"
+    // sama dengan index 12 + prev "3…", from "ab" + data "c" sama dengan
+    // from "a" + data "bc". Encoding kanonik memisahkan semuanya
+    #[test]
+    fn encoding_separates_fields() {
+        let old = |index: u32, prev: &str| format!("{}{}{}{}", index, prev, "[]", 0);
+        assert_eq!(old(1, "23ab"), old(12, "3ab"));
+
+        let header = |height: u32, prev_hash: Hash32| BlockHeader {
+            version: BLOCK_VERSION,
+            height,
+            prev_hash,
+            merkle_root: merkle_root(&[]),
+            timestamp: 0,
+            target: [0xff; 32],
+            nonce: 0,
+        };
+        let mut prev_a = [0; 32];
+        let mut prev_b = [0; 32];
+        prev_a[..2].copy_from_slice(&[0x23, 0xab]);
+        prev_b[..2].copy_from_slice(&[0x3a, 0xb0]);
+        let (a, b) = (header(1, prev_a), header(12, prev_b));
+        assert_eq!(a.encode().len(), b.encode().len());
+        assert_eq!(a.encode()[6..10], 1u32.to_be_bytes());
+        assert_eq!(b.encode()[6..10], 12u32.to_be_bytes());
+        assert_ne!(a.hash(), b.hash());
+
+        // transaksi: batas from / data / signature
+        let tx = |from: &str, data: &str, signature: &str| Transaction {
+            from: from.as_bytes().to_vec(),
+            data: data.into(),
+            signature: signature.as_bytes().to_vec(),
+        };
+        let (t1, t2) = (tx("ab", "c", ""), tx("a", "bc", ""));
+        assert_eq!([&t1.from[..], t1.data.as_bytes()].concat(), [&t2.from[..], t2.data.as_bytes()].concat());
+        assert_ne!(t1.encode(), t2.encode());
+        assert_ne!(t1.hash(), t2.hash());
+        assert_ne!(tx("a", "b", "c").encode_signed(), tx("a", "bc", "").encode_signed());
+
+        // body: dua transaksi tidak bisa menyamar jadi satu yang signature-nya
+        // memuat encoding transaksi kedua
+        let (t1, t2) = (tx("a", "b", "s"), tx("c", "d", "t"));
+        let glued = Transaction {
+            signature: [&t1.signature[..], &t2.encode_signed()].concat(),
+            ..t1.clone()
+        };
+        let block = |txs: Vec<Transaction>| Block { header: a.clone(), transactions: txs };
+        assert_ne!(block(vec![t1, t2]).encode(), block(vec![glued]).encode());
+    }
+
+    // signature tidak ikut txid (preimage tanda tangan), tapi ikut wtxid,
+    // jadi mengganti signature mengubah root dan menggagalkan bukti
+    #[test]
+    fn merkle_commits_signature() {
+        let mut txs = dummy_txs(4);
+        for tx in txs.iter_mut() {
+            tx.signature = vec![0xaa; 8];
+        }
+        let root = merkle_root(&txs);
+        let proof = MerkleProof::new(&txs, 2).expect("index dalam daftar");
+
+        let mut resigned = txs.clone();
+        resigned[2].signature[0] ^= 1;
+        assert_eq!(resigned[2].hash(), txs[2].hash());
+        assert_eq!(resigned[2].encode(), txs[2].encode());
+        assert_ne!(resigned[2].signed_hash(), txs[2].signed_hash());
+
+        assert_ne!(merkle_root(&resigned), root);
+        assert!(!proof.verify(&resigned[2], &root));
+        assert!(proof.verify(&txs[2], &root));
+    }
+}
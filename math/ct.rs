@@ -0,0 +1,96 @@
+// =====================
+// Constant-Time Helpers
+// masks are all-ones for "true" and zero for "false"
+// =====================
+
+// 0xff if a == b else 0x00, without branching on the data
+pub fn ct_eq(a: &[u8], b: &[u8]) -> u8 {
+    if a.len() != b.len() {
+        return 0;
+    }
+
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+
+    // diff == 0 -> 0xff, otherwise 0x00
+    ((diff as u16).wrapping_sub(1) >> 8) as u8
+}
+
+// -1 if a == b else 0
+pub fn ct_eq_u32(a: u32, b: u32) -> i32 {
+    // a ^ b == 0 is the only value whose decrement borrows out of 64 bits
+    -((((a ^ b) as u64).wrapping_sub(1) >> 63) as i32)
+}
+
+// r = mask ? a : b
+pub fn ct_select<const L: usize>(mask: u8, a: &[u8; L], b: &[u8; L]) -> [u8; L] {
+    let mut r = [0u8; L];
+    for ((r, x), y) in r.iter_mut().zip(a).zip(b) {
+        *r = y ^ (mask & (x ^ y));
+    }
+    r
+}
+
+// -1 if a < b else 0, for |a - b| < 2^31
+pub fn ct_lt(a: i32, b: i32) -> i32 {
+    (a - b) >> 31
+}
+
+// mask ? a : b
+pub fn ct_select_i32(mask: i32, a: i32, b: i32) -> i32 {
+    b ^ (mask & (a ^ b))
+}
+
+// =====================
+// Constant-Time Sort
+// djbsort's merging network: the compare-exchange sequence
+// depends only on the length, each exchange is a masked swap
+// =====================
+pub fn ct_sort_u32(x: &mut [u32]) {
+    let n = x.len();
+    if n < 2 {
+        return;
+    }
+
+    // (a, b) <- (min, max)
+    let minmax = |x: &mut [u32], i: usize, j: usize| {
+        let (a, b) = (x[i] as i64, x[j] as i64);
+        let swap = (b - a) >> 63; // all ones iff b < a
+        let t = swap & (a ^ b);
+        x[i] = (a ^ t) as u32;
+        x[j] = (b ^ t) as u32;
+    };
+
+    let mut top = 1;
+    while top < n - top {
+        top += top;
+    }
+
+    let mut p = top;
+    while p > 0 {
+        for i in 0..n - p {
+            if i & p == 0 {
+                minmax(x, i, i + p);
+            }
+        }
+
+        let mut i = 0;
+        let mut q = top;
+        while q > p {
+            while i < n - q {
+                if i & p == 0 {
+                    let mut r = q;
+                    while r > p {
+                        minmax(x, i + p, i + r);
+                        r >>= 1;
+                    }
+                }
+                i += 1;
+            }
+            q >>= 1;
+        }
+        p >>= 1;
+    }
+}
@@ -0,0 +1,9 @@
+pub mod ct;
+pub mod modulus;
+pub mod ntt;
+pub mod poly;
+pub mod polyvec;
+pub mod reduce;
+pub mod ring;
+pub mod sparse;
+pub mod toom;
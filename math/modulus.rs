@@ -0,0 +1,90 @@
+// =====================
+// Runtime Modulus
+// for schemes whose q is a parameter (crypto::lwe, and Poly
+// through its const Q):
+// a power-of-two q reduces with a mask, any other q with
+// a 64-bit Barrett step; neither branches on the value
+// =====================
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Modulus {
+    q: u64,
+    bits: u32,    // ceil(log2 q)
+    barrett: u64, // floor(2^64 / q), unused for powers of two
+}
+
+impl Modulus {
+    pub const fn new(q: u32) -> Self {
+        assert!(q >= 2, "modulus must be at least 2");
+
+        let q = q as u64;
+        let bits = 64 - (q - 1).leading_zeros();
+        let barrett = if q.is_power_of_two() { 0 } else { u64::MAX / q };
+
+        Self { q, bits, barrett }
+    }
+
+    pub const fn q(self) -> u32 {
+        self.q as u32
+    }
+
+    pub const fn bits(self) -> u32 {
+        self.bits
+    }
+
+    // (x / q, x mod q)
+    pub fn divrem(self, x: u64) -> (u64, i32) {
+        if self.barrett == 0 {
+            return (x >> self.bits, (x & (self.q - 1)) as i32);
+        }
+
+        // t is floor(x / q) or one less, so r < 2q
+        let t = ((x as u128 * self.barrett as u128) >> 64) as u64;
+        let r = x - t * self.q;
+
+        let d = r.wrapping_sub(self.q);
+        let borrow = d >> 63; // 1 iff r < q
+        let r = d.wrapping_add(self.q & borrow.wrapping_neg());
+
+        (t + 1 - borrow, r as i32)
+    }
+
+    pub fn reduce(self, x: u64) -> i32 {
+        self.divrem(x).1
+    }
+
+    // small signed value, |x| < q
+    pub fn lift(self, x: i32) -> i32 {
+        self.reduce((x as i64 + self.q as i64) as u64)
+    }
+
+    // any i64, sign handled with a mask
+    pub fn reduce_signed(self, x: i64) -> i32 {
+        let r = self.reduce(x.unsigned_abs()) as u64;
+        let neg = (x >> 63) as u64; // all ones iff x < 0
+
+        // q - r for negative x; reducing again maps q back to 0
+        self.reduce(((self.q - r) & neg) | (r & !neg))
+    }
+
+    // [0, q) -> (-q/2, q/2]
+    pub fn center(self, x: i32) -> i32 {
+        let half = (self.q / 2) as i32;
+        let above = ((half - x) >> 31) & 1; // 1 iff x > q/2
+        x - above * self.q as i32
+    }
+
+    // x^(q - 2) = 1 / x for prime q; the loop only walks the public exponent
+    pub fn inverse(self, x: i32) -> i32 {
+        let mut acc = 1u64;
+        let mut base = self.reduce(x as u64) as u64;
+        let mut e = self.q - 2;
+        while e > 0 {
+            if e & 1 == 1 {
+                acc = self.reduce(acc * base) as u64;
+            }
+            base = self.reduce(base * base) as u64;
+            e >>= 1;
+        }
+        acc as i32
+    }
+}
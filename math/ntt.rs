@@ -0,0 +1,186 @@
+use crate::math::reduce::{barrett_reduce, caddq, csubq, fqmul, to_mont};
+
+// =====================
+// NTT over Z_q[x]/(x^256 + 1), q = 3329
+// =====================
+//
+// q - 1 = 2^8 * 13, so Z_q only has 256-th roots of unity: the transform
+// stops after 7 layers and leaves 128 degree-1 residues modulo
+// x^2 - gamma_i, which are multiplied pairwise in `pointwise_mul`.
+
+pub const N: usize = 256;
+pub const Q: i32 = 3329;
+
+// primitive 256-th root of unity mod Q
+const ROOT: i32 = 17;
+
+// 128^-1 mod Q
+const N_INV: i32 = 3303;
+
+const fn pow_mod(base: i32, mut exp: u32) -> i32 {
+    let mut b = base as i64;
+    let mut r = 1i64;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            r = r * b % Q as i64;
+        }
+        b = b * b % Q as i64;
+        exp >>= 1;
+    }
+    r as i32
+}
+
+const fn bitrev7(x: usize) -> usize {
+    let mut r = 0;
+    let mut i = 0;
+    while i < 7 {
+        r |= ((x >> i) & 1) << (6 - i);
+        i += 1;
+    }
+    r
+}
+
+// ZETAS[i] = ROOT^bitrev7(i)
+const fn compute_zetas() -> [i32; 128] {
+    let mut z = [0i32; 128];
+    let mut i = 0;
+    while i < 128 {
+        z[i] = pow_mod(ROOT, bitrev7(i) as u32);
+        i += 1;
+    }
+    z
+}
+
+const fn compute_zetas_inv() -> [i32; 128] {
+    let mut z = [0i32; 128];
+    let mut i = 0;
+    while i < 128 {
+        z[i] = pow_mod(ZETAS[i], (Q - 2) as u32);
+        i += 1;
+    }
+    z
+}
+
+// GAMMAS[i] = ROOT^(2 * bitrev7(i) + 1), the modulus x^2 - GAMMAS[i] of pair i
+const fn compute_gammas() -> [i32; 128] {
+    let mut g = [0i32; 128];
+    let mut i = 0;
+    while i < 128 {
+        g[i] = pow_mod(ROOT, 2 * bitrev7(i) as u32 + 1);
+        i += 1;
+    }
+    g
+}
+
+const fn mont_table(t: [i32; 128]) -> [i32; 128] {
+    let mut m = [0i32; 128];
+    let mut i = 0;
+    while i < 128 {
+        m[i] = to_mont(t[i]);
+        i += 1;
+    }
+    m
+}
+
+pub const ZETAS: [i32; 128] = compute_zetas();
+pub const ZETAS_INV: [i32; 128] = compute_zetas_inv();
+pub const GAMMAS: [i32; 128] = compute_gammas();
+
+// twiddles in Montgomery form, so fqmul(x, ZETAS_MONT[k]) = x * ZETAS[k]
+const ZETAS_MONT: [i32; 128] = mont_table(ZETAS);
+const ZETAS_INV_MONT: [i32; 128] = mont_table(ZETAS_INV);
+const N_INV_MONT: i32 = to_mont(N_INV);
+
+// =====================
+// Forward NTT (in place)
+// input: any coefficients, reduced on entry
+// output: bit-reversed NTT domain, in [0, Q)
+// =====================
+pub fn ntt(a: &mut [i32; N]) {
+    for c in a.iter_mut() {
+        *c = barrett_reduce(*c as i64);
+    }
+
+    let mut k = 1;
+    let mut len = 128;
+
+    while len >= 2 {
+        let mut start = 0;
+        while start < N {
+            let zeta = ZETAS_MONT[k];
+            k += 1;
+
+            for j in start..start + len {
+                let t = fqmul(a[j + len], zeta);
+                a[j + len] = caddq(a[j] - t);
+                a[j] = csubq(a[j] + t);
+            }
+
+            start += 2 * len;
+        }
+        len >>= 1;
+    }
+}
+
+// =====================
+// Inverse NTT (in place)
+// =====================
+pub fn inv_ntt(a: &mut [i32; N]) {
+    let mut len = 2;
+
+    while len <= 128 {
+        // same twiddles the forward layer of this length used
+        let mut k = N / (2 * len);
+        let mut start = 0;
+        while start < N {
+            let zeta_inv = ZETAS_INV_MONT[k];
+            k += 1;
+
+            for j in start..start + len {
+                let t = a[j];
+                a[j] = csubq(t + a[j + len]);
+                a[j + len] = fqmul(caddq(t - a[j + len]), zeta_inv);
+            }
+
+            start += 2 * len;
+        }
+        len <<= 1;
+    }
+
+    for c in a.iter_mut() {
+        *c = fqmul(*c, N_INV_MONT);
+    }
+}
+
+// =====================
+// Pointwise Multiply (NTT domain)
+// (a0 + a1 x)(b0 + b1 x) mod (x^2 - gamma)
+// =====================
+pub fn pointwise_mul(a: &[i32; N], b: &[i32; N]) -> [i32; N] {
+    let mut r = [0i32; N];
+
+    for i in 0..N / 2 {
+        let (a0, a1) = (a[2 * i], a[2 * i + 1]);
+        let (b0, b1) = (b[2 * i], b[2 * i + 1]);
+
+        let a1b1 = barrett_reduce(a1 as i64 * b1 as i64) as i64;
+        r[2 * i] = barrett_reduce(a0 as i64 * b0 as i64 + a1b1 * GAMMAS[i] as i64);
+        r[2 * i + 1] = barrett_reduce(a0 as i64 * b1 as i64 + a1 as i64 * b0 as i64);
+    }
+
+    r
+}
+
+// =====================
+// Full Multiply in Z_q[x]/(x^256 + 1)
+// =====================
+pub fn mul(a: &[i32; N], b: &[i32; N]) -> [i32; N] {
+    let mut fa = *a;
+    let mut fb = *b;
+    ntt(&mut fa);
+    ntt(&mut fb);
+
+    let mut r = pointwise_mul(&fa, &fb);
+    inv_ntt(&mut r);
+    r
+}
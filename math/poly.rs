@@ -0,0 +1,304 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::math::modulus::Modulus;
+use crate::math::ntt;
+use crate::math::ring::Ring;
+use crate::math::toom;
+
+// =====================
+// Polynomial in Z_Q[x] / R
+// coefficients may hold any representative (the samplers write
+// centered values); every operation returns canonical ones in [0, Q)
+// =====================
+pub struct Poly<const N: usize, const Q: u32, R: Ring> {
+    pub coeffs: [i32; N],
+    ring: PhantomData<R>,
+}
+
+impl<const N: usize, const Q: u32, R: Ring> Poly<N, Q, R> {
+    pub const MODULUS: Modulus = Modulus::new(Q);
+
+    // centered inputs keep the Toom-Cook intermediates well inside i64
+    const TOOM_EXACT: bool = (N as u128) * (Q as u128 / 2).pow(2) < 1 << 40;
+
+    pub fn zero() -> Self {
+        Self::from_coeffs([0; N])
+    }
+
+    pub fn from_coeffs(coeffs: [i32; N]) -> Self {
+        Self { coeffs, ring: PhantomData }
+    }
+
+    // [0, Q)
+    pub fn to_canonical(&self) -> Self {
+        self.map(|c| Self::MODULUS.reduce_signed(c as i64))
+    }
+
+    // (-Q/2, Q/2]
+    pub fn to_centered(&self) -> Self {
+        self.map(|c| Self::MODULUS.center(Self::MODULUS.reduce_signed(c as i64)))
+    }
+
+    fn map(&self, f: impl Fn(i32) -> i32) -> Self {
+        Self::from_coeffs(self.coeffs.map(f))
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(i64, i64) -> i64) -> Self {
+        let mut r = Self::zero();
+        for (o, (&a, &b)) in r.coeffs.iter_mut().zip(self.coeffs.iter().zip(other.coeffs.iter())) {
+            *o = Self::MODULUS.reduce_signed(f(a as i64, b as i64));
+        }
+        r
+    }
+
+    fn add_poly(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a + b)
+    }
+
+    fn sub_poly(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a - b)
+    }
+
+    fn scale(&self, k: i32) -> Self {
+        self.map(|c| Self::MODULUS.reduce_signed(c as i64 * k as i64))
+    }
+
+    // NTT when the ring and (N, Q) allow it, Toom-Cook / Karatsuba
+    // for any other ring or modulus, schoolbook below the cutoffs
+    fn ring_mul(&self, other: &Self) -> Self {
+        if R::HAS_NTT && N == ntt::N && Q == ntt::Q as u32 {
+            let a: &[i32; ntt::N] = self.coeffs.as_slice().try_into().expect("N == ntt::N");
+            let b: &[i32; ntt::N] = other.coeffs.as_slice().try_into().expect("N == ntt::N");
+
+            let mut r = Self::zero();
+            r.coeffs.copy_from_slice(&ntt::mul(a, b));
+            return r;
+        }
+
+        if N > toom::KARATSUBA_CUTOFF && Self::TOOM_EXACT {
+            return self.mul_toom_cook(other);
+        }
+        self.mul_schoolbook(other)
+    }
+
+    // full product by `wide_mul`, then fold and reduce
+    fn mul_with(&self, other: &Self, wide_mul: fn(&[i64], &[i64]) -> Vec<i64>) -> Self {
+        // centered inputs keep the 2N-term sums well inside i64
+        let a: Vec<i64> = self.to_centered().coeffs.iter().map(|&c| c as i64).collect();
+        let b: Vec<i64> = other.to_centered().coeffs.iter().map(|&c| c as i64).collect();
+
+        // full product first: the fold below only looks at public indices
+        let mut res = wide_mul(&a, &b);
+        res.resize(2 * N, 0);
+        R::fold(&mut res, N);
+
+        let mut r = Self::zero();
+        for (o, &x) in r.coeffs.iter_mut().zip(res.iter()) {
+            *o = Self::MODULUS.reduce_signed(x);
+        }
+        r
+    }
+
+    // =====================
+    // Toom-Cook-4 / Karatsuba Multiply
+    // exact integer product, see math::toom
+    // =====================
+    pub fn mul_toom_cook(&self, other: &Self) -> Self {
+        self.mul_with(other, toom::mul)
+    }
+
+    // =====================
+    // Schoolbook Multiply (reference)
+    // O(N^2), the differential-test oracle for
+    // the NTT and Toom-Cook paths
+    // =====================
+    pub fn mul_schoolbook(&self, other: &Self) -> Self {
+        self.mul_with(other, toom::schoolbook)
+    }
+
+    // =====================
+    // Inverse (Q prime)
+    // Bernstein-Yang divsteps on the reversed polynomials, as in the
+    // NTRU Prime reference code: a fixed 2N - 1 steps with masked
+    // swaps, so the running time does not depend on the coefficients.
+    // None when self shares a factor with the ring polynomial
+    // =====================
+    pub fn inverse(&self) -> Option<Self> {
+        let m = Self::MODULUS;
+
+        // f = x^N - sum c_i x^i and g = self, both reversed
+        let mut f = vec![0i64; N + 1];
+        let mut g = vec![0i64; N + 1];
+        f[0] = 1;
+        for &(i, c) in R::TAIL {
+            f[N - i] = m.reduce_signed(-c) as i64;
+        }
+        for (i, &c) in self.coeffs.iter().enumerate() {
+            g[N - 1 - i] = m.reduce_signed(c as i64) as i64;
+        }
+
+        // v / r carry the Bezout coefficient of self for f / g
+        let mut v = vec![0i64; N + 1];
+        let mut r = vec![0i64; N + 1];
+        r[0] = 1;
+
+        let mut delta = 1i64;
+        for _ in 0..2 * N - 1 {
+            v.rotate_right(1);
+            v[0] = 0;
+
+            // all ones iff delta > 0 and g[0] != 0
+            let swap = (-delta >> 63) & ((g[0] | -g[0]) >> 63);
+            delta ^= swap & (delta ^ -delta);
+            delta += 1;
+
+            for i in 0..=N {
+                let t = swap & (f[i] ^ g[i]);
+                f[i] ^= t;
+                g[i] ^= t;
+                let t = swap & (v[i] ^ r[i]);
+                v[i] ^= t;
+                r[i] ^= t;
+            }
+
+            // cancel g[0] against f[0], then drop it
+            let (f0, g0) = (f[0], g[0]);
+            for i in 0..=N {
+                g[i] = m.reduce_signed(f0 * g[i] - g0 * f[i]) as i64;
+                r[i] = m.reduce_signed(f0 * r[i] - g0 * v[i]) as i64;
+            }
+            g.copy_within(1.., 0);
+            g[N] = 0;
+        }
+
+        // delta ends at 0 exactly when the gcd is a constant
+        if delta != 0 {
+            return None;
+        }
+
+        let scale = m.inverse(f[0] as i32) as i64;
+        let mut out = Self::zero();
+        for (i, o) in out.coeffs.iter_mut().enumerate() {
+            *o = m.reduce_signed(scale * v[N - 1 - i]);
+        }
+        Some(out)
+    }
+}
+
+impl<const N: usize, const Q: u32, R: Ring> Clone for Poly<N, Q, R> {
+    fn clone(&self) -> Self {
+        Self::from_coeffs(self.coeffs)
+    }
+}
+
+impl<const N: usize, const Q: u32, R: Ring> fmt::Debug for Poly<N, Q, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Poly").field("coeffs", &self.coeffs).finish()
+    }
+}
+
+// equal as elements of Z_Q[x] / R, whatever the representatives
+impl<const N: usize, const Q: u32, R: Ring> PartialEq for Poly<N, Q, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_canonical().coeffs == other.to_canonical().coeffs
+    }
+}
+
+impl<const N: usize, const Q: u32, R: Ring> Eq for Poly<N, Q, R> {}
+
+// =====================
+// Operators
+// every combination of owned / borrowed operands
+// =====================
+macro_rules! poly_binop {
+    ($op:ident, $method:ident, $assign_op:ident, $assign_method:ident, $imp:ident) => {
+        impl<const N: usize, const Q: u32, R: Ring> $op<&Poly<N, Q, R>> for &Poly<N, Q, R> {
+            type Output = Poly<N, Q, R>;
+
+            fn $method(self, other: &Poly<N, Q, R>) -> Poly<N, Q, R> {
+                self.$imp(other)
+            }
+        }
+
+        impl<const N: usize, const Q: u32, R: Ring> $op<Poly<N, Q, R>> for &Poly<N, Q, R> {
+            type Output = Poly<N, Q, R>;
+
+            fn $method(self, other: Poly<N, Q, R>) -> Poly<N, Q, R> {
+                self.$imp(&other)
+            }
+        }
+
+        impl<const N: usize, const Q: u32, R: Ring> $op<&Poly<N, Q, R>> for Poly<N, Q, R> {
+            type Output = Poly<N, Q, R>;
+
+            fn $method(self, other: &Poly<N, Q, R>) -> Poly<N, Q, R> {
+                self.$imp(other)
+            }
+        }
+
+        impl<const N: usize, const Q: u32, R: Ring> $op<Poly<N, Q, R>> for Poly<N, Q, R> {
+            type Output = Poly<N, Q, R>;
+
+            fn $method(self, other: Poly<N, Q, R>) -> Poly<N, Q, R> {
+                self.$imp(&other)
+            }
+        }
+
+        impl<const N: usize, const Q: u32, R: Ring> $assign_op<&Poly<N, Q, R>> for Poly<N, Q, R> {
+            fn $assign_method(&mut self, other: &Poly<N, Q, R>) {
+                *self = self.$imp(other);
+            }
+        }
+
+        impl<const N: usize, const Q: u32, R: Ring> $assign_op<Poly<N, Q, R>> for Poly<N, Q, R> {
+            fn $assign_method(&mut self, other: Poly<N, Q, R>) {
+                *self = self.$imp(&other);
+            }
+        }
+    };
+}
+
+poly_binop!(Add, add, AddAssign, add_assign, add_poly);
+poly_binop!(Sub, sub, SubAssign, sub_assign, sub_poly);
+poly_binop!(Mul, mul, MulAssign, mul_assign, ring_mul);
+
+impl<const N: usize, const Q: u32, R: Ring> Neg for &Poly<N, Q, R> {
+    type Output = Poly<N, Q, R>;
+
+    fn neg(self) -> Poly<N, Q, R> {
+        self.scale(-1)
+    }
+}
+
+impl<const N: usize, const Q: u32, R: Ring> Neg for Poly<N, Q, R> {
+    type Output = Poly<N, Q, R>;
+
+    fn neg(self) -> Poly<N, Q, R> {
+        self.scale(-1)
+    }
+}
+
+// scalar multiplication
+impl<const N: usize, const Q: u32, R: Ring> Mul<i32> for &Poly<N, Q, R> {
+    type Output = Poly<N, Q, R>;
+
+    fn mul(self, k: i32) -> Poly<N, Q, R> {
+        self.scale(k)
+    }
+}
+
+impl<const N: usize, const Q: u32, R: Ring> Mul<i32> for Poly<N, Q, R> {
+    type Output = Poly<N, Q, R>;
+
+    fn mul(self, k: i32) -> Poly<N, Q, R> {
+        self.scale(k)
+    }
+}
+
+impl<const N: usize, const Q: u32, R: Ring> MulAssign<i32> for Poly<N, Q, R> {
+    fn mul_assign(&mut self, k: i32) {
+        *self = self.scale(k);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::SchemePoly;
+
+// =====================
+// Vector of K Ring Elements
+// =====================
+#[derive(Clone, Debug)]
+pub struct PolyVec<const K: usize, const N: usize, const Q: u32> {
+    pub polys: [SchemePoly<N, Q>; K],
+}
+
+impl<const K: usize, const N: usize, const Q: u32> PolyVec<K, N, Q> {
+    pub fn from_fn(f: impl FnMut(usize) -> SchemePoly<N, Q>) -> Self {
+        Self { polys: std::array::from_fn(f) }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self::from_fn(|i| &self.polys[i] + &other.polys[i])
+    }
+
+    // inner product: sum_i self[i] * other[i]
+    pub fn dot(&self, other: &Self) -> SchemePoly<N, Q> {
+        let mut acc = SchemePoly::zero();
+        for (a, b) in self.polys.iter().zip(other.polys.iter()) {
+            acc += a * b;
+        }
+        acc
+    }
+}
+
+// =====================
+// K x K Matrix of Ring Elements
+// =====================
+#[derive(Clone, Debug)]
+pub struct PolyMatrix<const K: usize, const N: usize, const Q: u32> {
+    pub rows: [PolyVec<K, N, Q>; K],
+}
+
+impl<const K: usize, const N: usize, const Q: u32> PolyMatrix<K, N, Q> {
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> SchemePoly<N, Q>) -> Self {
+        Self {
+            rows: std::array::from_fn(|i| PolyVec::from_fn(|j| f(i, j))),
+        }
+    }
+
+    // A * v
+    pub fn mul_vec(&self, v: &PolyVec<K, N, Q>) -> PolyVec<K, N, Q> {
+        PolyVec::from_fn(|i| self.rows[i].dot(v))
+    }
+
+    // A^T * v
+    pub fn transpose_mul_vec(&self, v: &PolyVec<K, N, Q>) -> PolyVec<K, N, Q> {
+        PolyVec::from_fn(|j| {
+            let mut acc = SchemePoly::zero();
+            for (row, vi) in self.rows.iter().zip(v.polys.iter()) {
+                acc += &row.polys[j] * vi;
+            }
+            acc
+        })
+    }
+}
@@ -0,0 +1,59 @@
+// =====================
+// Modular Reduction mod Q
+// no data-dependent branches or divisions
+// =====================
+use crate::Q;
+
+// floor(2^64 / Q)
+const BARRETT_V: i128 = (1i128 << 64) / Q as i128;
+
+// 2^32 mod Q, the Montgomery factor R
+pub const MONT: i32 = ((1i64 << 32) % Q as i64) as i32;
+
+// Q^-1 mod 2^32, as a signed 32-bit word
+const QINV: i32 = {
+    // Newton iteration: x <- x * (2 - Q x) doubles the correct low bits
+    let q = Q as u64;
+    let mut x = 1u64;
+    let mut i = 0;
+    while i < 5 {
+        x = x.wrapping_mul(2u64.wrapping_sub(q.wrapping_mul(x)));
+        i += 1;
+    }
+    x as u32 as i32
+};
+
+// r - Q if r >= Q
+pub fn csubq(r: i32) -> i32 {
+    let r = r - Q;
+    r + ((r >> 31) & Q)
+}
+
+// r + Q if r < 0
+pub fn caddq(r: i32) -> i32 {
+    r + ((r >> 31) & Q)
+}
+
+// a mod Q in [0, Q) for any i64
+pub fn barrett_reduce(a: i64) -> i32 {
+    let t = ((a as i128 * BARRETT_V) >> 64) as i64;
+    let r = (a - t * Q as i64) as i32;
+    caddq(csubq(r))
+}
+
+// a * R^-1 mod Q in (-Q, Q), for |a| < Q * 2^31; only the low 32
+// bits of a enter t, so the multiply wraps instead of overflowing
+pub fn montgomery_reduce(a: i64) -> i32 {
+    let t = (a as i32).wrapping_mul(QINV) as i64;
+    ((a - t * Q as i64) >> 32) as i32
+}
+
+// a * b * R^-1 mod Q in [0, Q); pass b = x * R to get a * x
+pub fn fqmul(a: i32, b: i32) -> i32 {
+    caddq(montgomery_reduce(a as i64 * b as i64))
+}
+
+// x * R mod Q, for building Montgomery-domain constants
+pub const fn to_mont(x: i32) -> i32 {
+    ((x as i64 * MONT as i64) % Q as i64) as i32
+}
@@ -0,0 +1,50 @@
+// =====================
+// Reduction Rings
+// a ring is Z_q[x] / (x^n - sum c_i x^i); TAIL lists the (i, c_i)
+// so that x^n can be folded back onto low degrees
+// =====================
+pub trait Ring {
+    const NAME: &'static str;
+    const TAIL: &'static [(usize, i64)];
+
+    // x^n + 1 with q = 1 mod 2n splits completely, nothing else here does
+    const HAS_NTT: bool = false;
+
+    // full product of two degree < n polys -> n coefficients,
+    // top down so a fold into degree >= n is picked up again;
+    // only public indices decide where values go
+    fn fold(wide: &mut [i64], n: usize) {
+        for k in (n..wide.len()).rev() {
+            let val = wide[k];
+            wide[k] = 0;
+            for &(i, c) in Self::TAIL {
+                wide[k - n + i] += c * val;
+            }
+        }
+    }
+}
+
+// x^n + 1 (Kyber, Dilithium)
+pub struct Cyclotomic;
+
+impl Ring for Cyclotomic {
+    const NAME: &'static str = "x^n + 1";
+    const TAIL: &'static [(usize, i64)] = &[(0, -1)];
+    const HAS_NTT: bool = true;
+}
+
+// x^n - x + 1
+pub struct Trinomial;
+
+impl Ring for Trinomial {
+    const NAME: &'static str = "x^n - x + 1";
+    const TAIL: &'static [(usize, i64)] = &[(0, -1), (1, 1)];
+}
+
+// x^p - x - 1 (NTRU Prime), a field for suitable prime p
+pub struct NtruPrime;
+
+impl Ring for NtruPrime {
+    const NAME: &'static str = "x^p - x - 1";
+    const TAIL: &'static [(usize, i64)] = &[(0, 1), (1, 1)];
+}
@@ -0,0 +1,153 @@
+use std::ops::Mul;
+
+use zeroize::Zeroize;
+
+use crate::math::ct::{ct_eq_u32, ct_sort_u32};
+use crate::math::modulus::Modulus;
+use crate::math::poly::Poly;
+use crate::math::ring::Ring;
+
+// =====================
+// Sparse Ternary Polynomial
+// only the nonzero coefficients, as (index, +-1) in index order;
+// the weight is public (fixed by the parameter set), the
+// positions and signs are not
+// =====================
+pub struct SparseTernary<const N: usize> {
+    pub terms: Vec<(usize, i32)>,
+}
+
+impl<const N: usize> SparseTernary<N> {
+    // =====================
+    // Fixed Weight from Random Words
+    // the first h words get low bits 0b00 / 0b10 (-1 / +1), the rest
+    // 0b01 (0); a constant-time sort on the random high bits then
+    // spreads the h nonzero entries over uniformly random positions
+    // =====================
+    pub fn fixed_weight(h: usize, words: &[u32]) -> Self {
+        assert!(h <= N, "weight {} above degree {}", h, N);
+        assert_eq!(words.len(), N, "fixed_weight: need one word per coefficient");
+
+        let mut list: Vec<u32> = words
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| if i < h { x & !1 } else { (x & !3) | 1 })
+            .collect();
+        ct_sort_u32(&mut list);
+
+        let mut coeffs: Vec<i32> = list.iter().map(|&x| (x & 3) as i32 - 1).collect();
+        let sparse = Self::from_ternary(&coeffs);
+
+        list.zeroize();
+        coeffs.zeroize();
+        sparse
+    }
+
+    // coefficients in {-1, 0, 1}; the nonzero ones are gathered by
+    // sorting (is_zero, index, c + 1) keys, so no branch or address
+    // depends on where they sit
+    pub fn from_ternary(coeffs: &[i32]) -> Self {
+        assert_eq!(coeffs.len(), N, "from_ternary: need N coefficients");
+
+        let weight: usize = coeffs.iter().map(|&c| (c & 1) as usize).sum();
+
+        let mut keys: Vec<u32> = coeffs
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| ((((c & 1) ^ 1) as u32) << 31) | ((i as u32) << 2) | (c + 1) as u32)
+            .collect();
+        ct_sort_u32(&mut keys);
+
+        let terms = keys[..weight]
+            .iter()
+            .map(|&k| (((k >> 2) & 0x1fff_ffff) as usize, (k & 3) as i32 - 1))
+            .collect();
+        keys.zeroize();
+        Self { terms }
+    }
+
+    pub fn weight(&self) -> usize {
+        self.terms.len()
+    }
+
+    // every coefficient looks at every term, so no write lands at a
+    // secret index
+    pub fn to_poly<const Q: u32, R: Ring>(&self) -> Poly<N, Q, R> {
+        let mut p = Poly::zero();
+        for (i, c) in p.coeffs.iter_mut().enumerate() {
+            for &(j, s) in self.terms.iter() {
+                *c += s & ct_eq_u32(i as u32, j as u32);
+            }
+        }
+        p.to_canonical()
+    }
+
+    // =====================
+    // Sparse x Dense Multiply
+    // one signed copy of `a` per term, moved up by the term's index
+    // through a masked barrel shifter (one pass per index bit).
+    // Loop bounds and addresses depend only on N and the public
+    // weight, which costs O(weight · N · log N): slower than the
+    // dense NTT / Toom-Cook product at every weight the schemes use
+    // (~9x at sntrup761), so they multiply to_poly() instead
+    // =====================
+    pub fn mul_dense<const Q: u32, R: Ring>(&self, a: &Poly<N, Q, R>) -> Poly<N, Q, R> {
+        let m = Modulus::new(Q);
+        let a = a.to_centered();
+
+        // enough bits for any index below N
+        let bits = usize::BITS - N.saturating_sub(1).leading_zeros();
+
+        let mut wide = vec![0i64; 2 * N];
+        let mut shifted = vec![0i64; 2 * N];
+        for &(j, s) in self.terms.iter() {
+            shifted.fill(0);
+            for (d, &x) in shifted.iter_mut().zip(a.coeffs.iter()) {
+                *d = (s * x) as i64;
+            }
+
+            // shift by 2^b iff bit b of j is set; j < N keeps the
+            // result inside 2N slots
+            for b in 0..bits {
+                let shift = 1usize << b;
+                let mask = -(((j >> b) & 1) as i64);
+                for k in (shift..2 * N).rev() {
+                    shifted[k] ^= mask & (shifted[k] ^ shifted[k - shift]);
+                }
+                for x in shifted[..shift].iter_mut() {
+                    *x &= !mask;
+                }
+            }
+
+            for (w, &x) in wide.iter_mut().zip(shifted.iter()) {
+                *w += x;
+            }
+        }
+        shifted.zeroize();
+
+        R::fold(&mut wide, N);
+
+        let mut r = Poly::zero();
+        for (o, &x) in r.coeffs.iter_mut().zip(wide.iter()) {
+            *o = m.reduce_signed(x);
+        }
+        wide.zeroize();
+        r
+    }
+}
+
+impl<const N: usize, const Q: u32, R: Ring> Mul<&Poly<N, Q, R>> for &SparseTernary<N> {
+    type Output = Poly<N, Q, R>;
+
+    fn mul(self, a: &Poly<N, Q, R>) -> Poly<N, Q, R> {
+        self.mul_dense(a)
+    }
+}
+
+impl<const N: usize, const Q: u32, R: Ring> Mul<&SparseTernary<N>> for &Poly<N, Q, R> {
+    type Output = Poly<N, Q, R>;
+
+    fn mul(self, s: &SparseTernary<N>) -> Poly<N, Q, R> {
+        s.mul_dense(self)
+    }
+}
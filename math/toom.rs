@@ -0,0 +1,146 @@
+// =====================
+// Toom-Cook-4 / Karatsuba
+// full products over the integers (2n - 1 coefficients), for rings
+// and moduli without an NTT. One Toom-Cook-4 layer splits into 4
+// and needs 7 products instead of 16; Karatsuba takes those down
+// to the schoolbook cutoff. Everything is exact, so any q works and
+// every division in the interpolation leaves no remainder
+// =====================
+
+// below this Karatsuba's bookkeeping costs more than it saves
+pub const KARATSUBA_CUTOFF: usize = 16;
+
+// below this a Toom-Cook-4 layer is skipped
+pub const TOOM4_MIN: usize = 4 * KARATSUBA_CUTOFF;
+
+pub fn mul(a: &[i64], b: &[i64]) -> Vec<i64> {
+    assert_eq!(a.len(), b.len(), "toom: operands differ in length");
+
+    if a.len() >= TOOM4_MIN {
+        toom4(a, b)
+    } else {
+        karatsuba(a, b)
+    }
+}
+
+pub fn schoolbook(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let mut res = vec![0i64; (a.len() + b.len()).saturating_sub(1)];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            res[i + j] += x * y;
+        }
+    }
+    res
+}
+
+// `len` coefficients of a starting at `from`, zero-padded
+fn piece(a: &[i64], from: usize, len: usize) -> Vec<i64> {
+    let mut p = vec![0i64; len];
+    if from < a.len() {
+        let end = a.len().min(from + len);
+        p[..end - from].copy_from_slice(&a[from..end]);
+    }
+    p
+}
+
+fn add_into(acc: &mut [i64], x: &[i64]) {
+    for (a, &x) in acc.iter_mut().zip(x.iter()) {
+        *a += x;
+    }
+}
+
+// =====================
+// Karatsuba
+// a = lo + x^m hi: lo·lo, hi·hi and (lo + hi)(lo + hi) - both
+// =====================
+pub fn karatsuba(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let n = a.len();
+    if n <= KARATSUBA_CUTOFF {
+        return schoolbook(a, b);
+    }
+
+    let m = n.div_ceil(2);
+    let (a0, a1) = (piece(a, 0, m), piece(a, m, m));
+    let (b0, b1) = (piece(b, 0, m), piece(b, m, m));
+
+    let z0 = karatsuba(&a0, &b0);
+    let z2 = karatsuba(&a1, &b1);
+
+    let sum = |x: &[i64], y: &[i64]| -> Vec<i64> { x.iter().zip(y).map(|(x, y)| x + y).collect() };
+    let mut z1 = karatsuba(&sum(&a0, &a1), &sum(&b0, &b1));
+    for ((z1, &z0), &z2) in z1.iter_mut().zip(z0.iter()).zip(z2.iter()) {
+        *z1 -= z0 + z2;
+    }
+
+    // the padding of hi only adds zero coefficients past 2n - 1
+    let mut res = vec![0i64; 4 * m - 1];
+    add_into(&mut res, &z0);
+    add_into(&mut res[m..], &z1);
+    add_into(&mut res[2 * m..], &z2);
+    res.truncate(2 * n - 1);
+    res
+}
+
+// =====================
+// Toom-Cook-4
+// a = a0 + a1 y + a2 y^2 + a3 y^3 with y = x^m, evaluated at
+// 0, 1, -1, 2, -2, 1/2 (scaled by 8) and infinity
+// =====================
+fn toom4(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let n = a.len();
+    let m = n.div_ceil(4);
+
+    let eval = |x: &[i64]| -> [Vec<i64>; 7] {
+        let p: Vec<Vec<i64>> = (0..4).map(|i| piece(x, i * m, m)).collect();
+        let at = |f: &dyn Fn(i64, i64, i64, i64) -> i64| -> Vec<i64> {
+            (0..m).map(|j| f(p[0][j], p[1][j], p[2][j], p[3][j])).collect()
+        };
+        [
+            at(&|x0, _, _, _| x0),
+            at(&|x0, x1, x2, x3| x0 + x1 + x2 + x3),
+            at(&|x0, x1, x2, x3| x0 - x1 + x2 - x3),
+            at(&|x0, x1, x2, x3| x0 + 2 * x1 + 4 * x2 + 8 * x3),
+            at(&|x0, x1, x2, x3| x0 - 2 * x1 + 4 * x2 - 8 * x3),
+            at(&|x0, x1, x2, x3| 8 * x0 + 4 * x1 + 2 * x2 + x3),
+            at(&|_, _, _, x3| x3),
+        ]
+    };
+
+    let (ea, eb) = (eval(a), eval(b));
+    let w: Vec<Vec<i64>> = ea.iter().zip(eb.iter()).map(|(x, y)| karatsuba(x, y)).collect();
+
+    // interpolation, one coefficient position at a time:
+    // w = c0 + c1 y + ... + c6 y^6 at the seven points
+    let len = 2 * m - 1;
+    let mut c = vec![vec![0i64; len]; 7];
+    for j in 0..len {
+        let (r0, r1, r2, r3, r4, r5, r6) = (w[0][j], w[1][j], w[2][j], w[3][j], w[4][j], w[5][j], w[6][j]);
+
+        // even part: c2 + c4 and 4 c2 + 16 c4
+        let s1 = (r1 + r2) / 2 - r0 - r6;
+        let s2 = (r3 + r4) / 2 - r0 - 64 * r6;
+        let c4 = (s2 - 4 * s1) / 12;
+        let c2 = s1 - c4;
+
+        // odd part: c1 + c3 + c5, c1 + 4 c3 + 16 c5, 16 c1 + 4 c3 + c5
+        let o1 = (r1 - r2) / 2;
+        let o2 = (r3 - r4) / 4;
+        let o3 = (r5 - 64 * r0 - 16 * c2 - 4 * c4 - r6) / 2;
+        let u = (o2 - o1) / 3; // c3 + 5 c5
+        let v = (16 * o1 - o3) / 3; // 4 c3 + 5 c5
+        let c3 = (v - u) / 3;
+        let c5 = (u - c3) / 5;
+        let c1 = o1 - c3 - c5;
+
+        for (k, x) in [r0, c1, c2, c3, c4, c5, r6].into_iter().enumerate() {
+            c[k][j] = x;
+        }
+    }
+
+    let mut res = vec![0i64; 6 * m + len];
+    for (k, ck) in c.iter().enumerate() {
+        add_into(&mut res[k * m..], ck);
+    }
+    res.truncate(2 * n - 1);
+    res
+}